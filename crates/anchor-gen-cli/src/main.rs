@@ -0,0 +1,211 @@
+//! Command line tool which generates an Anchor CPI crate from a JSON IDL.
+//!
+//! Unlike the `anchor-gen` macros, the generated code is written to disk so it can be
+//! committed and reviewed.
+//!
+//! ```text
+//! anchor-gen generate --idl idl.json --out crates/govern-cpi
+//! ```
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+use anchor_idl::{idl_uses_int256, Builder, GeneratorError, GeneratorResult, Target, GEN_VERSION};
+use clap::{Parser, Subcommand};
+use heck::ToKebabCase;
+
+#[derive(Parser)]
+#[command(name = "anchor-gen", version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Generates a CPI crate from an IDL.
+    Generate(GenerateArgs),
+}
+
+#[derive(clap::Args)]
+struct GenerateArgs {
+    /// Path to the JSON IDL.
+    #[arg(long)]
+    idl: PathBuf,
+    /// Directory to write the crate to.
+    #[arg(long)]
+    out: PathBuf,
+    /// Name of the crate. Defaults to the name of the program.
+    #[arg(long)]
+    name: Option<String>,
+    /// Types to skip from generation. These should be provided by the caller instead.
+    #[arg(long, value_delimiter = ',')]
+    skip: Vec<String>,
+    /// Zero copy structs.
+    #[arg(long, value_delimiter = ',')]
+    zero_copy: Vec<String>,
+    /// `repr(packed)` structs.
+    #[arg(long, value_delimiter = ',')]
+    packed: Vec<String>,
+    /// Path to a TOML config file with additional options.
+    #[arg(long)]
+    config: Option<PathBuf>,
+    /// Framework which the generated code is written for: `anchor`, `no-anchor` or `pinocchio`.
+    #[arg(long)]
+    target: Option<String>,
+}
+
+fn write_file(path: &Path, contents: &str) -> GeneratorResult<()> {
+    fs::write(path, contents).map_err(|error| GeneratorError::WriteOutput {
+        path: path.to_path_buf(),
+        error,
+    })
+}
+
+fn generate(args: &GenerateArgs) -> GeneratorResult<()> {
+    // Resolve paths from the working directory rather than `CARGO_MANIFEST_DIR`,
+    // which is set when running through `cargo run`.
+    let resolve = |path: &Path| {
+        env::current_dir()
+            .map(|dir| dir.join(path))
+            .unwrap_or_else(|_| path.to_path_buf())
+    };
+
+    let mut builder = Builder::new(resolve(&args.idl));
+    if let Some(config) = &args.config {
+        builder = builder.config_path(resolve(config));
+    }
+    for name in &args.skip {
+        builder = builder.skip(name);
+    }
+    for name in &args.zero_copy {
+        builder = builder.zero_copy(name);
+    }
+    for name in &args.packed {
+        builder = builder.packed(name);
+    }
+    if let Some(target) = &args.target {
+        builder = builder.target(target.parse()?);
+    }
+
+    let generator = builder.to_generator()?;
+    let idl = &generator.idl;
+    let crate_name = args
+        .name
+        .clone()
+        .unwrap_or_else(|| idl.metadata.name.to_kebab_case());
+    let description = format!(
+        "Autogenerated CPI client for the {} program.",
+        idl.metadata.name
+    );
+    let generated_by = format!(
+        "This crate was automatically generated by\n\
+         [anchor-gen](https://github.com/saber-hq/anchor-gen) v{}, a crate for generating\n\
+         Anchor CPI helpers from JSON IDLs.",
+        GEN_VERSION.unwrap_or("unknown")
+    );
+
+    // Without Anchor, the 256-bit integer types implement `Pod` from bytemuck directly.
+    let uses_bytemuck = generator
+        .struct_opts
+        .values()
+        .any(|opts| opts.zero_copy || opts.packed)
+        || (generator.target != Target::Anchor && idl_uses_int256(idl));
+    let bytemuck = if !uses_bytemuck {
+        ""
+    } else {
+        "bytemuck = { version = \"1.23\", features = [\"derive\", \"min_const_generics\"] }\n"
+    };
+    let package = format!(
+        r#"[package]
+name = "{crate_name}"
+version = "{version}"
+edition = "2021"
+description = "{description}"
+"#,
+        version = idl.metadata.version,
+    );
+    let cargo_toml = match generator.target {
+        Target::Anchor => format!(
+            r#"{package}
+[features]
+default = ["cpi"]
+anchor-debug = []
+no-entrypoint = []
+no-idl = []
+no-log-ix-name = []
+cpi = ["no-entrypoint"]
+
+[dependencies]
+anchor-lang = "0.31.1"
+# Allows borsh to serialize arrays of any length
+borsh = {{ version = "0.10.3", features = ["const-generics"] }}
+{bytemuck}"#,
+        ),
+        Target::NoAnchor => format!(
+            r#"{package}
+[features]
+default = ["cpi"]
+cpi = []
+
+[dependencies]
+borsh = {{ version = "1.5", features = ["derive"] }}
+solana-program = "2.2"
+{bytemuck}"#,
+        ),
+        Target::Pinocchio => format!(
+            r#"{package}
+[features]
+default = ["cpi"]
+cpi = []
+
+[dependencies]
+borsh = {{ version = "1.5", features = ["derive"] }}
+pinocchio = "0.9"
+{bytemuck}"#,
+        ),
+    };
+
+    let doc_comment = |text: &str| {
+        text.lines()
+            .map(|line| {
+                if line.is_empty() {
+                    "//!\n".to_string()
+                } else {
+                    format!("//! {}\n", line)
+                }
+            })
+            .collect::<String>()
+    };
+    let lib_rs = format!(
+        "{}\n#![allow(clippy::too_many_arguments)]\n\n{}",
+        doc_comment(&format!("{}\n\n{}", description, generated_by)),
+        generator.generate_formatted()?
+    );
+
+    let readme = format!("# {}\n\n{}\n\n{}\n", crate_name, description, generated_by);
+
+    let src_dir = args.out.join("src");
+    fs::create_dir_all(&src_dir).map_err(|error| GeneratorError::WriteOutput {
+        path: src_dir.clone(),
+        error,
+    })?;
+    write_file(&args.out.join("Cargo.toml"), &cargo_toml)?;
+    write_file(&src_dir.join("lib.rs"), &lib_rs)?;
+    write_file(&args.out.join("README.md"), &readme)?;
+    Ok(())
+}
+
+fn main() {
+    let cli = Cli::parse();
+    let result = match &cli.command {
+        Command::Generate(args) => generate(args),
+    };
+    if let Err(err) = result {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,83 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::{self, Command, Output},
+};
+
+/// Gets an empty output directory which is unique to this test run.
+fn out_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("anchor-gen-cli-{}-{}", process::id(), name));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+fn anchor_gen(args: &[&str], out: &Path) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_anchor-gen"))
+        .arg("generate")
+        .args(args)
+        .arg("--out")
+        .arg(out)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn writes_a_crate() {
+    let out = out_dir("anchor");
+    let output = anchor_gen(
+        &[
+            "--idl",
+            "../../examples/govern-cpi/idl.json",
+            "--zero-copy",
+            "Governor",
+        ],
+        &out,
+    );
+    assert!(output.status.success(), "{:?}", output);
+
+    let cargo_toml = fs::read_to_string(out.join("Cargo.toml")).unwrap();
+    assert!(cargo_toml.contains("name = \"govern\""));
+    assert!(cargo_toml.contains("anchor-lang = "));
+    assert!(cargo_toml.contains("bytemuck = "));
+
+    let lib_rs = fs::read_to_string(out.join("src/lib.rs")).unwrap();
+    assert!(lib_rs.starts_with("//! Autogenerated CPI client for the govern program."));
+    assert!(lib_rs.contains("declare_id!(\"Govz1VyoyLD5BL6CSCxUJLVLsQHRwjfFj1prNsdNg5Jw\")"));
+    assert!(lib_rs.contains("#[account(zero_copy(unsafe)"));
+
+    assert!(out.join("README.md").exists());
+    fs::remove_dir_all(&out).unwrap();
+}
+
+#[test]
+fn writes_a_crate_for_a_target() {
+    let out = out_dir("no-anchor");
+    let output = anchor_gen(
+        &[
+            "--idl",
+            "../../examples/govern-cpi/idl.json",
+            "--name",
+            "govern-client",
+            "--target",
+            "no-anchor",
+        ],
+        &out,
+    );
+    assert!(output.status.success(), "{:?}", output);
+
+    let cargo_toml = fs::read_to_string(out.join("Cargo.toml")).unwrap();
+    assert!(cargo_toml.contains("name = \"govern-client\""));
+    assert!(cargo_toml.contains("solana-program = "));
+    assert!(!cargo_toml.contains("anchor-lang"));
+    fs::remove_dir_all(&out).unwrap();
+}
+
+#[test]
+fn reports_errors() {
+    let out = out_dir("missing");
+    let output = anchor_gen(&["--idl", "missing.json"], &out);
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.starts_with("error: "), "{}", stderr);
+    assert!(!out.exists());
+}
@@ -25,6 +25,72 @@
 //!
 //! Usage examples can be found in the [examples/](https://github.com/saber-hq/anchor-gen/tree/master/examples) directory.
 //!
+//! ## Decoding
+//!
+//! `instruction::Instruction::decode` decodes a raw instruction of the program from its data and account keys, including the accounts of composite groups and optional accounts.
+//!
+//! `state::AnyAccount::try_from_bytes` decodes the data of any account owned by the program, reading zero copy accounts with bytemuck and other accounts with Borsh.
+//!
+//! Events are decoded with `events::Event::try_from_log` for `Program data:` log lines written by `emit!`, or `events::Event::try_from_cpi_data` for the inner instructions written by `emit_cpi!`. `events::parse_logs` decodes all events of the program in the log messages of a transaction.
+//!
+//! ## Build scripts
+//!
+//! The generated code can also be written to a file from a `build.rs` script, which is easier to inspect and avoids re-parsing the IDL on every build:
+//!
+//! ```ignore
+//! // build.rs
+//! anchor_idl::Builder::new("idl.json").build().unwrap();
+//!
+//! // lib.rs
+//! include!(concat!(env!("OUT_DIR"), "/govern.rs"));
+//! ```
+//!
+//! ## Command line
+//!
+//! To commit generated code to a repository, the `anchor-gen` binary writes a complete crate with a `Cargo.toml`, `src/lib.rs` and README:
+//!
+//! ```text
+//! cargo install anchor-gen-cli
+//! anchor-gen generate --idl idl.json --out crates/govern-cpi --zero-copy Governor,Proposal
+//! ```
+//!
+//! ## Config files
+//!
+//! Options for large IDLs can be kept in a TOML file next to the IDL:
+//!
+//! ```toml
+//! zero_copy = ["Reserve", "Obligation"]
+//!
+//! [types.LendingMarket]
+//! zero_copy = true
+//! derives = ["PartialEq"]
+//! rename = "Market"
+//! ```
+//!
+//! ```skip
+//! anchor_gen::generate_cpi_interface!(idl_path = "idl.json", config_path = "anchor-gen.toml");
+//! ```
+//!
+//! Types whose IDL `serialization` is `bytemuck` are generated as zero copy, along with the types they reference, and a packed or aligned `repr` is kept. Setting `zero_copy` or `packed` on a type overrides what is in the IDL.
+//!
+//! The layouts of zero copy structs are checked at compile time against the sizes of their fields in the IDL, so a skipped type with a different layout fails to compile.
+//!
+//! Enums without fields can be given an integer representation with `repr = "u64"` and `first_discriminant = 1`. Zero copy enums default to `repr(u8)` and get a `Pod{Name}` wrapper, which zero copy structs store in place of the enum.
+//!
+//! The same file can be passed to the CLI with `--config anchor-gen.toml`.
+//!
+//! ## Without Anchor
+//!
+//! Programs which do not depend on Anchor can use `target = "no-anchor"`, either in the config file or as an option of `generate_cpi_interface!`:
+//!
+//! ```toml
+//! target = "no-anchor"
+//! ```
+//!
+//! The generated types only use `borsh` and `bytemuck`, and accounts, events and instructions get a `DISCRIMINATOR` constant. `instruction::<name>` builds a `solana_program::instruction::Instruction` from the accounts and arguments, and `cpi::<name>` invokes it with `invoke_signed`. The crate must depend on `borsh` 1, `bytemuck` and `solana-program` 2. The CLI writes these dependencies when given `--target no-anchor`.
+//!
+//! Programs written with Pinocchio can use `target = "pinocchio"` instead. The types are the same, with `Pubkey` being Pinocchio's byte array, and `cpi::<name>` takes a `cpi::accounts::<Name>` struct of `&AccountInfo`s. These helpers do not allocate: the instruction data is serialized into a fixed-size buffer, and the accounts are passed to `invoke_signed` as arrays. Instructions whose arguments have no maximum size, such as strings and vectors, take a `buffer: &mut [u8]` to serialize the data into instead. The crate must depend on `borsh` 1 and `pinocchio`.
+//!
 //! Note: This does not work on legacy IDLs. To migrate a legacy IDL, use `anchor idl convert idl.json`.
 
 pub use anchor_generate_cpi_crate::generate_cpi_crate;
@@ -15,6 +15,7 @@
 //! More examples can be found in the [examples/](https://github.com/saber-hq/anchor-gen/tree/master/examples) directory.
 
 use anchor_idl::GeneratorOptions;
+use darling::util::SpannedValue;
 use syn::{parse_macro_input, LitStr};
 
 /// Generates an Anchor CPI crate from a JSON file.
@@ -41,8 +42,11 @@ use syn::{parse_macro_input, LitStr};
 pub fn generate_cpi_crate(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let id_literal = parse_macro_input!(input as LitStr);
     let opts = GeneratorOptions {
-        idl_path: id_literal.value(),
+        idl_path: SpannedValue::new(id_literal.value(), id_literal.span()),
         ..Default::default()
     };
-    opts.to_generator().generate_cpi_interface().into()
+    opts.to_generator()
+        .and_then(|generator| generator.generate_cpi_interface())
+        .unwrap_or_else(|err| err.to_compile_error(opts.idl_path.span()))
+        .into()
 }
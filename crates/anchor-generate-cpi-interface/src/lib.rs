@@ -24,6 +24,8 @@ use syn::parse_macro_input;
 /// # Arguments
 ///
 /// * `idl_path` - Path to a JSON IDL relative to the crate's the Cargo.toml.
+/// * `config_path` - Path to a TOML config file relative to the crate's Cargo.toml, with
+///   options for individual types. See `anchor_idl::GeneratorConfig`.
 ///
 /// # Examples
 ///
@@ -36,6 +38,27 @@ use syn::parse_macro_input;
 ///     voting_period: 0,
 ///     voting_delay: 0,
 /// };
+/// assert_eq!(u32::from(errors::ErrorCode::InvalidVoteSide), 6000);
+/// assert_eq!(
+///     errors::ErrorCode::try_from(anchor_lang::error::Error::from(errors::ErrorCode::GovernorNotFound)),
+///     Ok(errors::ErrorCode::GovernorNotFound)
+/// );
+/// let governor = Pubkey::new_unique();
+/// let (proposal, _) = pda::find_proposal_address(governor, 0);
+/// assert_eq!(
+///     proposal,
+///     Pubkey::find_program_address(
+///         &[b"\"TribecaProposal\"", governor.as_ref(), &0u64.to_le_bytes()],
+///         &ID,
+///     )
+///     .0
+/// );
+/// let event = events::ProposalCancelEvent { governor, proposal };
+/// let data = anchor_lang::Event::data(&event);
+/// assert!(matches!(
+///     events::Event::try_from_bytes(&data)?,
+///     Some(events::Event::ProposalCancelEvent(events::ProposalCancelEvent { proposal: p, .. })) if p == proposal
+/// ));
 /// #   Ok(())
 /// # }
 /// ```
@@ -48,5 +71,9 @@ pub fn generate_cpi_interface(input: proc_macro::TokenStream) -> TokenStream {
             return TokenStream::from(e.write_errors());
         }
     };
-    parsed.to_generator().generate_cpi_interface().into()
+    parsed
+        .to_generator()
+        .and_then(|generator| generator.generate_cpi_interface())
+        .unwrap_or_else(|err| err.to_compile_error(parsed.idl_path.span()))
+        .into()
 }
@@ -1,12 +1,26 @@
 pub use anchor_lang_idl_spec::*;
 use heck::{ToPascalCase, ToSnakeCase};
-use proc_macro2::TokenStream;
+use proc_macro2::{Ident, TokenStream};
 use quote::{format_ident, quote};
 
+use crate::Target;
+
+/// Gets the field name and struct name of a composite account group.
+fn composite_names(name: &str, inner: &IdlInstructionAccounts) -> (Ident, String) {
+    (
+        format_ident!("{}{}", name, inner.name.to_snake_case()),
+        format!("{}{}", name, inner.name.to_pascal_case()),
+    )
+}
+
 /// Generates a list of [IdlAccountItem]s as a [TokenStream].
+///
+/// With Anchor, the fields are those of a `#[derive(Accounts)]` struct. With Pinocchio,
+/// each field is a reference to an `AccountInfo`.
 pub fn generate_account_fields(
     name: &str,
     accounts: &[IdlInstructionAccountItem],
+    target: Target,
 ) -> (TokenStream, TokenStream) {
     let mut all_structs: Vec<TokenStream> = vec![];
     let all_fields = accounts
@@ -14,13 +28,16 @@ pub fn generate_account_fields(
         .map(|account| match account {
             IdlInstructionAccountItem::Single(info) => {
                 let acc_name = format_ident!("{}", info.name.to_snake_case());
-                let annotation = if info.writable {
+                let docs = crate::generate_docs(&info.docs);
+                let annotation = if info.writable && target == Target::Anchor {
                     quote! { #[account(mut)] }
                 } else {
                     quote! {}
                 };
                 let ty = {
-                    let acc_type = if info.signer {
+                    let acc_type = if target == Target::Pinocchio {
+                        quote! { &'info AccountInfo }
+                    } else if info.signer {
                         quote! { Signer<'info> }
                     } else {
                         quote! { AccountInfo<'info> }
@@ -33,18 +50,24 @@ pub fn generate_account_fields(
                     }
                 };
                 quote! {
+                   #docs
                    #annotation
                    pub #acc_name: #ty
                 }
             }
             IdlInstructionAccountItem::Composite(inner) => {
-                let field_name = format_ident!("{}{}", name, inner.name.to_snake_case());
-                let sub_name = format!("{}{}", name, inner.name.to_pascal_case());
+                let (field_name, sub_name) = composite_names(name, inner);
                 let sub_ident = format_ident!("{}", &sub_name);
-                let (sub_structs, sub_fields) = generate_account_fields(&sub_name, &inner.accounts);
+                let (sub_structs, sub_fields) =
+                    generate_account_fields(&sub_name, &inner.accounts, target);
+                let derive = if target == Target::Pinocchio {
+                    quote! { #[derive(Clone, Copy)] }
+                } else {
+                    quote! { #[derive(Accounts)] }
+                };
                 all_structs.push(sub_structs);
                 all_structs.push(quote! {
-                    #[derive(Accounts)]
+                    #derive
                     pub struct #sub_ident<'info> {
                         #sub_fields
                     }
@@ -64,3 +87,335 @@ pub fn generate_account_fields(
         },
     )
 }
+
+/// Flattens the accounts of the structs generated by [generate_account_fields] in order,
+/// along with the path of each account's field from `path`.
+pub fn flatten_account_fields<'a>(
+    name: &str,
+    accounts: &'a [IdlInstructionAccountItem],
+    path: &TokenStream,
+    out: &mut Vec<(TokenStream, &'a IdlInstructionAccount)>,
+) {
+    for account in accounts {
+        match account {
+            IdlInstructionAccountItem::Single(info) => {
+                let acc_name = format_ident!("{}", info.name.to_snake_case());
+                out.push((quote! { #path.#acc_name }, info));
+            }
+            IdlInstructionAccountItem::Composite(inner) => {
+                let (field_name, sub_name) = composite_names(name, inner);
+                flatten_account_fields(
+                    &sub_name,
+                    &inner.accounts,
+                    &quote! { #path.#field_name },
+                    out,
+                );
+            }
+        }
+    }
+}
+
+/// Parses a fixed account address into its bytes.
+pub fn parse_fixed_address(address: &str) -> Option<Vec<u8>> {
+    bs58::decode(address)
+        .into_vec()
+        .ok()
+        .filter(|bytes| bytes.len() == 32)
+}
+
+/// Finds the account type which holds the `child` account as a field, given the
+/// name of the `parent` instruction account it is related to.
+///
+/// An account type named after the parent is preferred; otherwise the type must be
+/// the only account with a matching field.
+fn find_relation_type(
+    defs: &[IdlTypeDef],
+    account_defs: &[IdlAccount],
+    parent: &str,
+    child: &str,
+) -> Option<String> {
+    let candidates = account_defs
+        .iter()
+        .filter(|account| {
+            defs.iter().any(|def| {
+                def.name == account.name
+                    && matches!(
+                        &def.ty,
+                        IdlTypeDefTy::Struct {
+                            fields: Some(IdlDefinedFields::Named(fields)),
+                        } if fields.iter().any(|field| {
+                            field.name.to_snake_case() == child.to_snake_case()
+                                && field.ty == IdlType::Pubkey
+                        })
+                    )
+            })
+        })
+        .map(|account| account.name.clone())
+        .collect::<Vec<_>>();
+    let parent_type = parent.to_pascal_case();
+    if candidates.contains(&parent_type) {
+        Some(parent_type)
+    } else if candidates.len() == 1 {
+        candidates.into_iter().next()
+    } else {
+        None
+    }
+}
+
+/// Generates an off-chain client struct of [Pubkey]s implementing [anchor_lang::ToAccountMetas],
+/// along with the structs of its composite accounts. Without Anchor, `to_account_metas`
+/// is an inherent method instead.
+///
+/// Accounts with a fixed `address` are not fields of the struct, unless they are optional.
+/// If an account is related to another account of the struct whose type is known, or an
+/// optional account has a fixed address, a `new` constructor is generated which reads the
+/// account from the fetched parent account or sets it to its address.
+///
+/// Only the Anchor and no-anchor targets have client structs.
+pub fn generate_client_accounts_struct(
+    defs: &[IdlTypeDef],
+    account_defs: &[IdlAccount],
+    name: &str,
+    docs: &[String],
+    accounts: &[IdlInstructionAccountItem],
+    target: Target,
+) -> TokenStream {
+    let struct_name = format_ident!("{}", name);
+    let mut all_structs: Vec<TokenStream> = vec![];
+    let mut all_fields: Vec<TokenStream> = vec![];
+    let mut all_metas: Vec<TokenStream> = vec![];
+    let mut ctor_params: Vec<TokenStream> = vec![];
+    let mut ctor_data_params: Vec<TokenStream> = vec![];
+    let mut ctor_fields: Vec<TokenStream> = vec![];
+    let mut key_reads: Vec<TokenStream> = vec![];
+    let mut field_names: Vec<Ident> = vec![];
+    let mut has_ctor = false;
+
+    for account in accounts {
+        match account {
+            IdlInstructionAccountItem::Single(info) => {
+                let acc_name = format_ident!("{}", info.name.to_snake_case());
+                let signer = info.signer;
+                let account_meta = if target == Target::Anchor {
+                    quote! { anchor_lang::solana_program::instruction::AccountMeta }
+                } else {
+                    quote! { solana_program::instruction::AccountMeta }
+                };
+                let meta = if info.writable {
+                    quote! { #account_meta::new }
+                } else {
+                    quote! { #account_meta::new_readonly }
+                };
+
+                let fixed_address = info.address.as_deref().and_then(parse_fixed_address);
+                if let (Some(bytes), false) = (&fixed_address, info.optional) {
+                    all_metas.push(quote! {
+                        account_metas.push(#meta(Pubkey::new_from_array([#(#bytes),*]), #signer));
+                    });
+                    key_reads.push(quote! {
+                        next_account_key(keys)?;
+                    });
+                    continue;
+                }
+
+                let docs = crate::generate_docs(&info.docs);
+                let ty = if info.optional {
+                    // Missing optional accounts are replaced by the program ID.
+                    all_metas.push(quote! {
+                        match self.#acc_name {
+                            Some(key) => account_metas.push(#meta(key, #signer)),
+                            None => account_metas.push(#account_meta::new_readonly(ID, false)),
+                        }
+                    });
+                    key_reads.push(quote! {
+                        let key = next_account_key(keys)?;
+                        let #acc_name = (key != ID).then_some(key);
+                    });
+                    quote! { Option<Pubkey> }
+                } else {
+                    key_reads.push(quote! {
+                        let #acc_name = next_account_key(keys)?;
+                    });
+                    all_metas.push(quote! {
+                        account_metas.push(#meta(self.#acc_name, #signer));
+                    });
+                    quote! { Pubkey }
+                };
+                all_fields.push(quote! {
+                    #docs
+                    pub #acc_name: #ty
+                });
+                field_names.push(acc_name.clone());
+
+                if let Some(bytes) = fixed_address {
+                    has_ctor = true;
+                    ctor_fields.push(quote! {
+                        #acc_name: Some(Pubkey::new_from_array([#(#bytes),*]))
+                    });
+                    continue;
+                }
+
+                let relation = info.relations.iter().find_map(|parent| {
+                    let parent_is_field = accounts.iter().any(|other| {
+                        matches!(other, IdlInstructionAccountItem::Single(other)
+                            if &other.name == parent && !other.optional)
+                    });
+                    if !parent_is_field {
+                        return None;
+                    }
+                    find_relation_type(defs, account_defs, parent, &info.name)
+                        .map(|parent_type| (parent, parent_type))
+                });
+                match relation {
+                    Some((parent, parent_type)) => {
+                        has_ctor = true;
+                        let data_name = format_ident!("{}_data", parent.to_snake_case());
+                        let parent_type = format_ident!("{}", parent_type);
+                        let data_param = quote! { #data_name: &state::#parent_type };
+                        let data_param_str = data_param.to_string();
+                        if !ctor_data_params
+                            .iter()
+                            .any(|p| p.to_string() == data_param_str)
+                        {
+                            ctor_data_params.push(data_param);
+                        }
+                        let value = if info.optional {
+                            quote! { Some(#data_name.#acc_name) }
+                        } else {
+                            quote! { #data_name.#acc_name }
+                        };
+                        ctor_fields.push(quote! { #acc_name: #value });
+                    }
+                    None => {
+                        ctor_params.push(quote! { #acc_name: #ty });
+                        ctor_fields.push(quote! { #acc_name });
+                    }
+                }
+            }
+            IdlInstructionAccountItem::Composite(inner) => {
+                let (field_name, sub_name) = composite_names(name, inner);
+                let sub_ident = format_ident!("{}", &sub_name);
+                all_structs.push(generate_client_accounts_struct(
+                    defs,
+                    account_defs,
+                    &sub_name,
+                    &[],
+                    &inner.accounts,
+                    target,
+                ));
+                all_metas.push(if target == Target::Anchor {
+                    quote! {
+                        account_metas.extend(anchor_lang::ToAccountMetas::to_account_metas(
+                            &self.#field_name,
+                            None,
+                        ));
+                    }
+                } else {
+                    quote! {
+                        account_metas.extend(self.#field_name.to_account_metas());
+                    }
+                });
+                all_fields.push(quote! {
+                    pub #field_name: #sub_ident
+                });
+                key_reads.push(quote! {
+                    let #field_name = #sub_ident::from_account_keys(keys)?;
+                });
+                field_names.push(field_name.clone());
+                ctor_params.push(quote! { #field_name: #sub_ident });
+                ctor_fields.push(quote! { #field_name });
+            }
+        }
+    }
+
+    let keys_unused = if key_reads.is_empty() {
+        quote! {
+            let _ = keys;
+        }
+    } else {
+        quote! {}
+    };
+    let docs = crate::generate_docs(docs);
+    let ctor = if has_ctor {
+        let doc = format!(
+            " Creates a new [{}], reading related accounts from the fetched account data \
+             and setting optional accounts with a fixed address to it.",
+            name
+        );
+        quote! {
+            impl #struct_name {
+                #[doc = #doc]
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(#(#ctor_params,)* #(#ctor_data_params),*) -> Self {
+                    Self {
+                        #(#ctor_fields),*
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Metas are pushed one by one since optional accounts may be left out. Without any
+    // accounts, the vector is never pushed to.
+    let (to_account_metas, result) = if target == Target::Anchor {
+        (
+            quote! {
+                impl anchor_lang::ToAccountMetas for #struct_name {
+                    #[allow(clippy::vec_init_then_push, unused_mut)]
+                    fn to_account_metas(
+                        &self,
+                        _is_signer: Option<bool>,
+                    ) -> Vec<anchor_lang::solana_program::instruction::AccountMeta> {
+                        let mut account_metas = vec![];
+                        #(#all_metas)*
+                        account_metas
+                    }
+                }
+            },
+            quote! { Result<Self> },
+        )
+    } else {
+        (
+            quote! {
+                impl #struct_name {
+                    /// Gets the metas of the accounts, in the order expected by the instruction.
+                    #[allow(clippy::vec_init_then_push, unused_mut)]
+                    pub fn to_account_metas(&self) -> Vec<solana_program::instruction::AccountMeta> {
+                        let mut account_metas = vec![];
+                        #(#all_metas)*
+                        account_metas
+                    }
+                }
+            },
+            quote! { std::result::Result<Self, ProgramError> },
+        )
+    };
+
+    quote! {
+        #(#all_structs)*
+
+        #docs
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        pub struct #struct_name {
+            #(#all_fields),*
+        }
+
+        #to_account_metas
+
+        impl #struct_name {
+            /// Reads the accounts from the account keys of an instruction, in order.
+            /// The keys of the accounts which were read are removed from `keys`.
+            pub fn from_account_keys(keys: &mut &[Pubkey]) -> #result {
+                #keys_unused
+                #(#key_reads)*
+                Ok(Self {
+                    #(#field_names),*
+                })
+            }
+        }
+
+        #ctor
+    }
+}
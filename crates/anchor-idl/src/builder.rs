@@ -0,0 +1,157 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{load_idl, Generator, GeneratorConfig, GeneratorError, GeneratorResult, Target};
+
+/// Generates a CPI crate from a `build.rs` script.
+///
+/// The generated code is formatted and written to `OUT_DIR`, from where it can be
+/// included in the crate:
+///
+/// ```no_run
+/// // build.rs
+/// anchor_idl::Builder::new("idl.json")
+///     .zero_copy("Governor")
+///     .build()
+///     .unwrap();
+/// ```
+///
+/// ```ignore
+/// // lib.rs
+/// include!(concat!(env!("OUT_DIR"), "/govern.rs"));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Builder {
+    idl_path: PathBuf,
+    file_name: Option<String>,
+    config_path: Option<PathBuf>,
+    config: GeneratorConfig,
+}
+
+impl Builder {
+    /// Creates a [Builder] for the IDL at the given path.
+    ///
+    /// Relative paths are resolved from the directory of the crate's Cargo.toml.
+    pub fn new(idl_path: impl Into<PathBuf>) -> Self {
+        Builder {
+            idl_path: idl_path.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the name of the file written to `OUT_DIR`.
+    ///
+    /// Defaults to the name of the program, e.g. `govern.rs`.
+    pub fn file_name(mut self, file_name: impl Into<String>) -> Self {
+        self.file_name = Some(file_name.into());
+        self
+    }
+
+    /// Reads additional options from a TOML config file. See [GeneratorConfig].
+    ///
+    /// Relative paths are resolved like the IDL path.
+    pub fn config_path(mut self, config_path: impl Into<PathBuf>) -> Self {
+        self.config_path = Some(config_path.into());
+        self
+    }
+
+    /// Skips generating a type. It should be provided by the caller instead.
+    pub fn skip(mut self, name: impl Into<String>) -> Self {
+        self.config.skip.push(name.into());
+        self
+    }
+
+    /// Generates a type as a zero copy struct.
+    pub fn zero_copy(mut self, name: impl Into<String>) -> Self {
+        self.config.zero_copy.push(name.into());
+        self
+    }
+
+    /// Generates a type as a `repr(packed)` struct.
+    pub fn packed(mut self, name: impl Into<String>) -> Self {
+        self.config.packed.push(name.into());
+        self
+    }
+
+    /// Derives an additional trait on a type.
+    pub fn derive(mut self, name: impl Into<String>, derive: impl Into<String>) -> Self {
+        let config = self.config.types.entry(name.into()).or_default();
+        config.derives.push(derive.into());
+        self
+    }
+
+    /// Renames a type.
+    pub fn rename(mut self, name: impl Into<String>, rename: impl Into<String>) -> Self {
+        let config = self.config.types.entry(name.into()).or_default();
+        config.rename = Some(rename.into());
+        self
+    }
+
+    /// Sets the framework which the generated code is written for. Defaults to Anchor.
+    pub fn target(mut self, target: Target) -> Self {
+        self.config.target = Some(target);
+        self
+    }
+
+    fn resolve_path(path: &Path) -> PathBuf {
+        match env::var_os("CARGO_MANIFEST_DIR") {
+            Some(dir) if path.is_relative() => PathBuf::from(dir).join(path),
+            _ => path.to_path_buf(),
+        }
+    }
+
+    /// Creates the [Generator] for the IDL.
+    pub fn to_generator(&self) -> GeneratorResult<Generator> {
+        let idl = load_idl(&Self::resolve_path(&self.idl_path))?;
+        // Options set on the builder take precedence over the config file.
+        let mut config = match &self.config_path {
+            Some(config_path) => GeneratorConfig::load(&Self::resolve_path(config_path))?,
+            None => GeneratorConfig::default(),
+        };
+        config.merge(self.config.clone());
+        Generator::new(idl, &config)
+    }
+
+    /// Generates the formatted code of the CPI crate.
+    pub fn generate(&self) -> GeneratorResult<String> {
+        self.to_generator()?.generate_formatted()
+    }
+
+    /// Writes the formatted code of the CPI crate to the given path.
+    pub fn write_to(&self, path: &Path) -> GeneratorResult<()> {
+        Self::write_generated(&self.to_generator()?, path)
+    }
+
+    fn write_generated(generator: &Generator, path: &Path) -> GeneratorResult<()> {
+        let code = generator.generate_formatted()?;
+        fs::write(path, code).map_err(|error| GeneratorError::WriteOutput {
+            path: path.to_path_buf(),
+            error,
+        })
+    }
+
+    /// Writes the formatted code of the CPI crate to `OUT_DIR`, and tells Cargo to
+    /// rerun the build script if the IDL changes.
+    ///
+    /// Returns the path of the written file.
+    pub fn build(&self) -> GeneratorResult<PathBuf> {
+        let out_dir = env::var_os("OUT_DIR").ok_or(GeneratorError::MissingOutDir)?;
+        let idl_path = Self::resolve_path(&self.idl_path);
+        println!("cargo:rerun-if-changed={}", idl_path.display());
+        if let Some(config_path) = &self.config_path {
+            let config_path = Self::resolve_path(config_path);
+            println!("cargo:rerun-if-changed={}", config_path.display());
+        }
+
+        let generator = self.to_generator()?;
+        let file_name = self
+            .file_name
+            .clone()
+            .unwrap_or_else(|| format!("{}.rs", generator.idl.metadata.name));
+        let path = PathBuf::from(out_dir).join(file_name);
+        Self::write_generated(&generator, &path)?;
+        Ok(path)
+    }
+}
@@ -0,0 +1,258 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use anchor_lang_idl_spec::{
+    Idl, IdlDefinedFields, IdlGenericArg, IdlInstructionAccountItem, IdlSeed, IdlType, IdlTypeDefTy,
+};
+use serde::Deserialize;
+
+use crate::{GeneratorError, GeneratorResult, Target};
+
+/// Integer types which may be used as the `repr` of an enum.
+const ENUM_REPRS: &[&str] = &["u8", "u16", "u32", "u64", "i8", "i16", "i32", "i64"];
+
+/// Options for generating a crate, usually loaded from an `anchor-gen.toml` file.
+///
+/// Types may be listed in the top-level lists, or configured individually:
+///
+/// ```toml
+/// zero_copy = ["Reserve", "Obligation"]
+///
+/// [types.LendingMarket]
+/// zero_copy = true
+/// derives = ["PartialEq"]
+/// rename = "Market"
+/// ```
+///
+/// Types are always referenced by their name in the IDL, and configuring a type which is
+/// not in the IDL is an error. Zero copy and packed layouts are inferred from the IDL,
+/// and the options here override them.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GeneratorConfig {
+    /// Types to skip from generation. These should be provided by the caller instead.
+    pub skip: Vec<String>,
+    /// Zero copy structs.
+    pub zero_copy: Vec<String>,
+    /// `repr(packed)` structs.
+    pub packed: Vec<String>,
+    /// Framework which the generated code is written for. Defaults to Anchor.
+    pub target: Option<Target>,
+    /// Options of individual types.
+    pub types: BTreeMap<String, TypeConfig>,
+}
+
+/// Options of a single type.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TypeConfig {
+    /// Skips generating the type. It should be provided by the caller instead.
+    pub skip: bool,
+    /// Generates the type as a zero copy struct. Defaults to the layout in the IDL.
+    pub zero_copy: Option<bool>,
+    /// Generates the type as a `repr(packed)` struct. Defaults to the layout in the IDL.
+    pub packed: Option<bool>,
+    /// Additional traits to derive on the type.
+    pub derives: Vec<String>,
+    /// Name of the generated Rust type, if different from the IDL.
+    pub rename: Option<String>,
+    /// Integer representation of an enum without fields, e.g. `u64`.
+    /// Zero copy enums default to `u8`.
+    pub repr: Option<String>,
+    /// Discriminant of the first variant of an enum without fields. Defaults to 0.
+    pub first_discriminant: Option<i64>,
+}
+
+impl GeneratorConfig {
+    /// Reads and parses a TOML config file.
+    pub fn load(path: &Path) -> GeneratorResult<Self> {
+        let contents = fs::read_to_string(path).map_err(|error| GeneratorError::ReadConfig {
+            path: path.to_path_buf(),
+            error,
+        })?;
+        toml::from_str(&contents).map_err(|error| GeneratorError::ParseConfig {
+            path: path.to_path_buf(),
+            message: error.to_string(),
+        })
+    }
+
+    /// Adds the options of another config to this one.
+    ///
+    /// Options set in both configs are taken from `other`.
+    pub fn merge(&mut self, other: GeneratorConfig) {
+        self.skip.extend(other.skip);
+        self.zero_copy.extend(other.zero_copy);
+        self.packed.extend(other.packed);
+        self.target = other.target.or(self.target);
+        for (name, other) in other.types {
+            let config = self.types.entry(name).or_default();
+            config.skip |= other.skip;
+            config.zero_copy = other.zero_copy.or(config.zero_copy);
+            config.packed = other.packed.or(config.packed);
+            config.derives.extend(other.derives);
+            if other.rename.is_some() {
+                config.rename = other.rename;
+            }
+            config.repr = other.repr.or(config.repr.take());
+            config.first_discriminant = other.first_discriminant.or(config.first_discriminant);
+        }
+    }
+
+    /// Gets the options of a type, combining the top-level lists with its section.
+    pub fn type_config(&self, name: &str) -> TypeConfig {
+        let mut config = self.types.get(name).cloned().unwrap_or_default();
+        let contains = |list: &[String]| list.iter().any(|el| el == name);
+        config.skip |= contains(&self.skip);
+        if contains(&self.zero_copy) {
+            config.zero_copy = Some(true);
+        }
+        if contains(&self.packed) {
+            config.packed = Some(true);
+        }
+        config
+    }
+
+    /// Checks that all types with options are in the IDL, and that all derives, renames
+    /// and reprs are valid Rust paths and types.
+    pub fn validate(&self, idl: &Idl) -> GeneratorResult<()> {
+        for (name, config) in &self.types {
+            let in_idl = idl.types.iter().any(|def| &def.name == name)
+                || idl.accounts.iter().any(|account| &account.name == name);
+            if !in_idl {
+                return Err(GeneratorError::UnknownType(name.clone()));
+            }
+            if let Some(derive) = config
+                .derives
+                .iter()
+                .find(|derive| syn::parse_str::<syn::Path>(derive).is_err())
+            {
+                return Err(GeneratorError::InvalidTypePath {
+                    option: "derives",
+                    path: derive.clone(),
+                });
+            }
+            if let Some(rename) = &config.rename {
+                if syn::parse_str::<syn::Ident>(rename).is_err() {
+                    return Err(GeneratorError::InvalidTypePath {
+                        option: "rename",
+                        path: rename.clone(),
+                    });
+                }
+            }
+            if let Some(repr) = &config.repr {
+                if !ENUM_REPRS.contains(&repr.as_str()) {
+                    return Err(GeneratorError::InvalidRepr {
+                        ty: name.clone(),
+                        repr: repr.clone(),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Gets the renamed types, from their IDL name to their Rust name.
+    pub fn renames(&self) -> BTreeMap<String, String> {
+        self.types
+            .iter()
+            .filter_map(|(name, config)| Some((name.clone(), config.rename.clone()?)))
+            .collect()
+    }
+}
+
+fn rename_type(ty: &mut IdlType, renames: &BTreeMap<String, String>) {
+    match ty {
+        IdlType::Defined { name, generics } => {
+            if let Some(rename) = renames.get(name) {
+                *name = rename.clone();
+            }
+            for arg in generics {
+                if let IdlGenericArg::Type { ty } = arg {
+                    rename_type(ty, renames);
+                }
+            }
+        }
+        IdlType::Option(inner) | IdlType::Vec(inner) | IdlType::Array(inner, _) => {
+            rename_type(inner, renames)
+        }
+        _ => {}
+    }
+}
+
+fn rename_fields(fields: &mut Option<IdlDefinedFields>, renames: &BTreeMap<String, String>) {
+    match fields {
+        Some(IdlDefinedFields::Named(fields)) => fields
+            .iter_mut()
+            .for_each(|field| rename_type(&mut field.ty, renames)),
+        Some(IdlDefinedFields::Tuple(fields)) => {
+            fields.iter_mut().for_each(|ty| rename_type(ty, renames))
+        }
+        None => {}
+    }
+}
+
+fn rename_seed_accounts(
+    accounts: &mut [IdlInstructionAccountItem],
+    renames: &BTreeMap<String, String>,
+) {
+    for item in accounts {
+        match item {
+            IdlInstructionAccountItem::Single(account) => {
+                let seeds = account
+                    .pda
+                    .iter_mut()
+                    .flat_map(|pda| pda.seeds.iter_mut().chain(pda.program.as_mut()));
+                for seed in seeds {
+                    if let IdlSeed::Account(seed) = seed {
+                        if let Some(name) = &mut seed.account {
+                            rename_name(name, renames);
+                        }
+                    }
+                }
+            }
+            IdlInstructionAccountItem::Composite(inner) => {
+                rename_seed_accounts(&mut inner.accounts, renames)
+            }
+        }
+    }
+}
+
+fn rename_name(name: &mut String, renames: &BTreeMap<String, String>) {
+    if let Some(rename) = renames.get(name) {
+        *name = rename.clone();
+    }
+}
+
+/// Renames types and all references to them in an IDL.
+pub fn rename_types(idl: &mut Idl, renames: &BTreeMap<String, String>) {
+    if renames.is_empty() {
+        return;
+    }
+    for def in &mut idl.types {
+        rename_name(&mut def.name, renames);
+        match &mut def.ty {
+            IdlTypeDefTy::Struct { fields } => rename_fields(fields, renames),
+            IdlTypeDefTy::Enum { variants } => variants
+                .iter_mut()
+                .for_each(|variant| rename_fields(&mut variant.fields, renames)),
+            IdlTypeDefTy::Type { alias } => rename_type(alias, renames),
+        }
+    }
+    for account in &mut idl.accounts {
+        rename_name(&mut account.name, renames);
+    }
+    for event in &mut idl.events {
+        rename_name(&mut event.name, renames);
+    }
+    for ix in &mut idl.instructions {
+        ix.args
+            .iter_mut()
+            .for_each(|arg| rename_type(&mut arg.ty, renames));
+        if let Some(returns) = &mut ix.returns {
+            rename_type(returns, renames);
+        }
+        rename_seed_accounts(&mut ix.accounts, renames);
+    }
+    for constant in &mut idl.constants {
+        rename_type(&mut constant.ty, renames);
+    }
+}
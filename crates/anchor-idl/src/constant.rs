@@ -0,0 +1,278 @@
+use anchor_lang_idl_spec::{IdlArrayLen, IdlConst, IdlType};
+use proc_macro2::{Literal, TokenStream};
+use quote::{format_ident, quote};
+use syn::{Expr, Lit, UnOp};
+
+use crate::{GeneratorError, GeneratorResult, Target};
+
+/// Generates a single constant.
+pub fn generate_constant(constant: &IdlConst, target: Target) -> GeneratorResult<TokenStream> {
+    let name = format_ident!("{}", constant.name);
+    let ty = const_type_to_rust_type(&constant.ty)
+        .map_err(|reason| GeneratorError::unsupported(&constant.name, reason))?;
+    let value = syn::parse_str::<Expr>(&constant.value).ok();
+    let value = const_value_to_tokens(&constant.ty, value.as_ref(), &constant.value, target)
+        .map_err(|reason| {
+            GeneratorError::unsupported(
+                &constant.name,
+                format!("cannot generate value `{}`: {}", constant.value, reason),
+            )
+        })?;
+    let docs = crate::generate_docs(&constant.docs);
+    Ok(quote! {
+        #docs
+        pub const #name: #ty = #value;
+    })
+}
+
+/// Generates all constants.
+pub fn generate_constants(constants: &[IdlConst], target: Target) -> GeneratorResult<TokenStream> {
+    let defined = constants
+        .iter()
+        .map(|constant| generate_constant(constant, target))
+        .collect::<GeneratorResult<Vec<_>>>()?;
+    Ok(quote! {
+        #(#defined)*
+    })
+}
+
+/// Gets the type of a constant, which must be usable in a `const` item.
+fn const_type_to_rust_type(ty: &IdlType) -> Result<TokenStream, String> {
+    Ok(match ty {
+        IdlType::Bytes => quote! { &[u8] },
+        IdlType::String => quote! { &str },
+        IdlType::Array(inner, IdlArrayLen::Value(len)) => {
+            let inner = const_type_to_rust_type(inner)?;
+            quote! { [#inner; #len] }
+        }
+        IdlType::Bool
+        | IdlType::U8
+        | IdlType::I8
+        | IdlType::U16
+        | IdlType::I16
+        | IdlType::U32
+        | IdlType::I32
+        | IdlType::F32
+        | IdlType::U64
+        | IdlType::I64
+        | IdlType::F64
+        | IdlType::U128
+        | IdlType::I128
+        | IdlType::U256
+        | IdlType::I256
+        | IdlType::Pubkey => crate::ty_to_rust_type(ty)
+            .map_err(|error| error.to_string())?
+            .parse()
+            .unwrap(),
+        _ => return Err(format!("unsupported constant type {:?}", ty)),
+    })
+}
+
+/// Converts the value of a constant to a Rust expression of the given type.
+///
+/// `expr` is the value parsed as a Rust expression, if possible, and `raw` is the
+/// original string from the IDL.
+fn const_value_to_tokens(
+    ty: &IdlType,
+    expr: Option<&Expr>,
+    raw: &str,
+    target: Target,
+) -> Result<TokenStream, String> {
+    let expr = expr.map(strip_expr);
+    match ty {
+        IdlType::Bool => match expr {
+            Some(Expr::Lit(lit)) => match &lit.lit {
+                Lit::Bool(b) => return Ok(quote! { #b }),
+                _ => Err("expected a boolean".to_string()),
+            },
+            _ => Err("expected a boolean".to_string()),
+        },
+        IdlType::U8 => parse_unsigned(expr, u8::MAX.into()).map(|v| Literal::u8_suffixed(v as u8)),
+        IdlType::U16 => {
+            parse_unsigned(expr, u16::MAX.into()).map(|v| Literal::u16_suffixed(v as u16))
+        }
+        IdlType::U32 => {
+            parse_unsigned(expr, u32::MAX.into()).map(|v| Literal::u32_suffixed(v as u32))
+        }
+        IdlType::U64 => {
+            parse_unsigned(expr, u64::MAX.into()).map(|v| Literal::u64_suffixed(v as u64))
+        }
+        IdlType::U128 => parse_unsigned(expr, u128::MAX).map(Literal::u128_suffixed),
+        IdlType::I8 => parse_signed(expr, i8::MIN.into(), i8::MAX.into())
+            .map(|v| Literal::i8_suffixed(v as i8)),
+        IdlType::I16 => parse_signed(expr, i16::MIN.into(), i16::MAX.into())
+            .map(|v| Literal::i16_suffixed(v as i16)),
+        IdlType::I32 => parse_signed(expr, i32::MIN.into(), i32::MAX.into())
+            .map(|v| Literal::i32_suffixed(v as i32)),
+        IdlType::I64 => parse_signed(expr, i64::MIN.into(), i64::MAX.into())
+            .map(|v| Literal::i64_suffixed(v as i64)),
+        IdlType::I128 => parse_signed(expr, i128::MIN, i128::MAX).map(Literal::i128_suffixed),
+        IdlType::U256 | IdlType::I256 => {
+            let signed = matches!(ty, IdlType::I256);
+            let (negative, expr) = match expr {
+                Some(Expr::Unary(unary)) if signed && matches!(unary.op, UnOp::Neg(_)) => {
+                    (true, Some(strip_expr(&unary.expr)))
+                }
+                _ => (false, expr),
+            };
+            let limbs = match expr {
+                Some(Expr::Lit(lit)) => match &lit.lit {
+                    Lit::Int(int) => crate::parse_int256_limbs(int.base10_digits(), negative),
+                    _ => None,
+                },
+                _ => None,
+            }
+            .ok_or_else(|| "expected a 256-bit integer".to_string())?;
+            // The sign bit must match the sign of the value.
+            let sign_bit = limbs[3] >> 63 == 1;
+            if (signed && sign_bit != negative && limbs != [0; 4]) || (!signed && negative) {
+                return Err(format!("{} is out of range", raw));
+            }
+            let ty_name: TokenStream = crate::ty_to_rust_type(ty)
+                .map_err(|error| error.to_string())?
+                .parse()
+                .unwrap();
+            return Ok(quote! { #ty_name([#(#limbs),*]) });
+        }
+        IdlType::F32 => parse_float(expr).map(|v| Literal::f32_suffixed(v as f32)),
+        IdlType::F64 => parse_float(expr).map(Literal::f64_suffixed),
+        IdlType::String => match expr {
+            Some(Expr::Lit(lit)) => match &lit.lit {
+                Lit::Str(s) => Ok(Literal::string(&s.value())),
+                _ => Err("expected a string".to_string()),
+            },
+            // Strings are sometimes stored without quotes.
+            _ => Ok(Literal::string(raw)),
+        },
+        IdlType::Bytes => {
+            let bytes = parse_bytes(expr)?;
+            return Ok(quote! { &[#(#bytes),*] });
+        }
+        IdlType::Pubkey => {
+            let address = match expr {
+                Some(Expr::Lit(lit)) => match &lit.lit {
+                    Lit::Str(s) => s.value(),
+                    _ => return Err("expected a base58 address".to_string()),
+                },
+                Some(Expr::Macro(mac)) => syn::parse2::<syn::LitStr>(mac.mac.tokens.clone())
+                    .map_err(|_| "expected a base58 address".to_string())?
+                    .value(),
+                _ => raw.to_string(),
+            };
+            let bytes = bs58::decode(&address)
+                .into_vec()
+                .map_err(|e| format!("invalid base58 address: {}", e))?;
+            if bytes.len() != 32 {
+                return Err(format!("expected 32 bytes, got {}", bytes.len()));
+            }
+            return Ok(target.pubkey(&bytes));
+        }
+        IdlType::Array(inner, IdlArrayLen::Value(len)) => {
+            let elements = match expr {
+                Some(Expr::Array(arr)) => arr
+                    .elems
+                    .iter()
+                    .map(|el| {
+                        const_value_to_tokens(inner, Some(el), &quote!(#el).to_string(), target)
+                    })
+                    .collect::<Result<Vec<_>, _>>()?,
+                Some(Expr::Lit(lit)) if matches!(**inner, IdlType::U8) => match &lit.lit {
+                    Lit::ByteStr(s) => s.value().into_iter().map(|b| quote! { #b }).collect(),
+                    _ => return Err("expected an array".to_string()),
+                },
+                _ => return Err("expected an array".to_string()),
+            };
+            if elements.len() != *len {
+                return Err(format!("expected {} elements, got {}", len, elements.len()));
+            }
+            return Ok(quote! { [#(#elements),*] });
+        }
+        _ => Err(format!("unsupported constant type {:?}", ty)),
+    }
+    .map(|lit| quote! { #lit })
+}
+
+/// Removes references, parentheses and groups around an expression.
+fn strip_expr(expr: &Expr) -> &Expr {
+    match expr {
+        Expr::Reference(inner) => strip_expr(&inner.expr),
+        Expr::Paren(inner) => strip_expr(&inner.expr),
+        Expr::Group(inner) => strip_expr(&inner.expr),
+        _ => expr,
+    }
+}
+
+fn parse_unsigned(expr: Option<&Expr>, max: u128) -> Result<u128, String> {
+    match expr {
+        Some(Expr::Lit(lit)) => match &lit.lit {
+            Lit::Int(int) => {
+                let value = int.base10_parse::<u128>().map_err(|e| e.to_string())?;
+                if value > max {
+                    Err(format!("{} is out of range", value))
+                } else {
+                    Ok(value)
+                }
+            }
+            _ => Err("expected an integer".to_string()),
+        },
+        _ => Err("expected an integer".to_string()),
+    }
+}
+
+fn parse_signed(expr: Option<&Expr>, min: i128, max: i128) -> Result<i128, String> {
+    let (negative, expr) = match expr {
+        Some(Expr::Unary(unary)) if matches!(unary.op, UnOp::Neg(_)) => {
+            (true, Some(strip_expr(&unary.expr)))
+        }
+        _ => (false, expr),
+    };
+    // The magnitude of `i128::MIN` does not fit in an `i128`.
+    let magnitude = parse_unsigned(expr, u128::MAX)?;
+    let value = if negative {
+        0i128.checked_sub_unsigned(magnitude)
+    } else {
+        i128::try_from(magnitude).ok()
+    };
+    match value {
+        Some(value) if value >= min && value <= max => Ok(value),
+        _ => Err(format!(
+            "{}{} is out of range",
+            if negative { "-" } else { "" },
+            magnitude
+        )),
+    }
+}
+
+fn parse_float(expr: Option<&Expr>) -> Result<f64, String> {
+    let (negative, expr) = match expr {
+        Some(Expr::Unary(unary)) if matches!(unary.op, UnOp::Neg(_)) => {
+            (true, Some(strip_expr(&unary.expr)))
+        }
+        _ => (false, expr),
+    };
+    let value = match expr {
+        Some(Expr::Lit(lit)) => match &lit.lit {
+            Lit::Float(f) => f.base10_parse::<f64>().map_err(|e| e.to_string())?,
+            Lit::Int(int) => int.base10_parse::<f64>().map_err(|e| e.to_string())?,
+            _ => return Err("expected a number".to_string()),
+        },
+        _ => return Err("expected a number".to_string()),
+    };
+    Ok(if negative { -value } else { value })
+}
+
+fn parse_bytes(expr: Option<&Expr>) -> Result<Vec<u8>, String> {
+    match expr {
+        Some(Expr::Lit(lit)) => match &lit.lit {
+            Lit::ByteStr(s) => Ok(s.value()),
+            Lit::Str(s) => Ok(s.value().into_bytes()),
+            _ => Err("expected a byte array".to_string()),
+        },
+        Some(Expr::Array(arr)) => arr
+            .elems
+            .iter()
+            .map(|el| parse_unsigned(Some(strip_expr(el)), u8::MAX.into()).map(|v| v as u8))
+            .collect(),
+        _ => Err("expected a byte array".to_string()),
+    }
+}
@@ -0,0 +1,149 @@
+use anchor_lang_idl_spec::IdlErrorCode;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+use crate::Target;
+
+/// Generates the program's error enum from a list of [IdlErrorCode]s.
+pub fn generate_errors(errors: &[IdlErrorCode], target: Target) -> TokenStream {
+    if errors.is_empty() {
+        return quote! {};
+    }
+
+    let variants = errors.iter().map(|error| {
+        let name = format_ident!("{}", error.name);
+        let code = error.code;
+        let doc = match &error.msg {
+            Some(msg) => format!(" {} - {}", code, msg),
+            None => format!(" {}", code),
+        };
+        quote! {
+            #[doc = #doc]
+            #name = #code
+        }
+    });
+    let name_arms = errors.iter().map(|error| {
+        let name = format_ident!("{}", error.name);
+        let name_str = &error.name;
+        quote! {
+            ErrorCode::#name => #name_str
+        }
+    });
+    let msg_arms = errors.iter().map(|error| {
+        let name = format_ident!("{}", error.name);
+        let msg = error.msg.as_deref().unwrap_or(&error.name);
+        quote! {
+            ErrorCode::#name => #msg
+        }
+    });
+    let from_code_arms = errors.iter().map(|error| {
+        let name = format_ident!("{}", error.name);
+        let code = error.code;
+        quote! {
+            #code => Ok(ErrorCode::#name)
+        }
+    });
+
+    let conversions = match target {
+        Target::Anchor => quote! {
+            impl From<ErrorCode> for anchor_lang::error::Error {
+                fn from(error_code: ErrorCode) -> anchor_lang::error::Error {
+                    anchor_lang::error::Error::from(anchor_lang::error::AnchorError {
+                        error_name: error_code.name().to_string(),
+                        error_code_number: error_code.into(),
+                        error_msg: error_code.msg().to_string(),
+                        error_origin: None,
+                        compared_values: None,
+                    })
+                }
+            }
+
+            impl TryFrom<anchor_lang::error::Error> for ErrorCode {
+                type Error = anchor_lang::error::Error;
+
+                fn try_from(
+                    error: anchor_lang::error::Error,
+                ) -> std::result::Result<Self, anchor_lang::error::Error> {
+                    let code = match &error {
+                        anchor_lang::error::Error::AnchorError(e) => e.error_code_number,
+                        anchor_lang::error::Error::ProgramError(e) => match e.program_error {
+                            ProgramError::Custom(code) => code,
+                            _ => return Err(error),
+                        },
+                    };
+                    ErrorCode::try_from(code).map_err(|_| error)
+                }
+            }
+        },
+        Target::NoAnchor | Target::Pinocchio => quote! {
+            impl From<ErrorCode> for ProgramError {
+                fn from(error_code: ErrorCode) -> ProgramError {
+                    ProgramError::Custom(error_code.into())
+                }
+            }
+
+            impl TryFrom<ProgramError> for ErrorCode {
+                type Error = ProgramError;
+
+                fn try_from(error: ProgramError) -> std::result::Result<Self, ProgramError> {
+                    match error {
+                        ProgramError::Custom(code) => ErrorCode::try_from(code).map_err(|_| error),
+                        _ => Err(error),
+                    }
+                }
+            }
+        },
+    };
+
+    quote! {
+        /// Errors returned by the program.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        #[repr(u32)]
+        pub enum ErrorCode {
+            #(#variants),*
+        }
+
+        impl ErrorCode {
+            /// Gets the name of this [ErrorCode].
+            pub fn name(&self) -> &'static str {
+                match self {
+                    #(#name_arms),*
+                }
+            }
+
+            /// Gets the message of this [ErrorCode].
+            pub fn msg(&self) -> &'static str {
+                match self {
+                    #(#msg_arms),*
+                }
+            }
+        }
+
+        impl From<ErrorCode> for u32 {
+            fn from(e: ErrorCode) -> u32 {
+                e as u32
+            }
+        }
+
+        impl TryFrom<u32> for ErrorCode {
+            type Error = u32;
+
+            fn try_from(code: u32) -> std::result::Result<Self, u32> {
+                match code {
+                    #(#from_code_arms,)*
+                    _ => Err(code),
+                }
+            }
+        }
+
+        #conversions
+
+        impl std::fmt::Display for ErrorCode {
+            fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                fmt.write_str(self.msg())
+            }
+        }
+
+        impl std::error::Error for ErrorCode {}
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
-    fields::{generate_struct_fields, get_idl_defined_fields_as_slice},
-    get_field_list_properties, StructOpts,
+    fields::generate_struct_body, get_defined_fields_list_properties, GeneratorError,
+    GeneratorResult, StructOpts, Target,
 };
 use anchor_lang_idl_spec::{IdlDefinedFields, IdlEvent, IdlTypeDef};
 use proc_macro2::TokenStream;
@@ -12,10 +12,14 @@ use syn::Ident;
 pub fn generate_event(
     defs: &[IdlTypeDef],
     struct_name: &Ident,
+    discriminator: &[u8],
+    docs: &[String],
     fields: &Option<IdlDefinedFields>,
-) -> TokenStream {
-    let fields_rendered = generate_struct_fields(fields);
-    let props = get_field_list_properties(defs, get_idl_defined_fields_as_slice(fields));
+    opts: &StructOpts,
+    target: Target,
+) -> GeneratorResult<TokenStream> {
+    let body = generate_struct_body(fields)?;
+    let props = get_defined_fields_list_properties(defs, fields)?;
 
     let derive_default = if props.can_derive_default {
         quote! {
@@ -25,14 +29,31 @@ pub fn generate_event(
         quote! {}
     };
 
-    quote! {
-        #[event]
-        #[derive(Debug)]
-        #derive_default
-        pub struct #struct_name {
-            #fields_rendered
-        }
-    }
+    let discriminator = crate::generate_discriminator(discriminator);
+    let extra_derives = opts.generate_derives();
+    let docs = crate::generate_docs(docs);
+    Ok(match target {
+        Target::Anchor => quote! {
+            #docs
+            #[event(discriminator = #discriminator)]
+            #[derive(Debug)]
+            #derive_default
+            #extra_derives
+            pub struct #struct_name #body
+        },
+        Target::NoAnchor | Target::Pinocchio => quote! {
+            #docs
+            #[derive(::borsh::BorshSerialize, ::borsh::BorshDeserialize, Debug)]
+            #derive_default
+            #extra_derives
+            pub struct #struct_name #body
+
+            impl #struct_name {
+                /// Discriminator at the start of the event's data.
+                pub const DISCRIMINATOR: &'static [u8] = &#discriminator;
+            }
+        },
+    })
 }
 
 /// Generates event structs.
@@ -40,22 +61,155 @@ pub fn generate_events(
     events: &[IdlEvent],
     typedefs: &[IdlTypeDef],
     struct_opts: &BTreeMap<String, StructOpts>,
-) -> TokenStream {
-    let defined = events.iter().map(|def| {
-        let struct_name = format_ident!("{}", def.name);
-        let opts = struct_opts.get(&def.name).copied().unwrap_or_default();
-        if opts.skip {
-            quote! {}
-        } else {
-            let typedef = typedefs.iter().find(|d| d.name == def.name).unwrap();
+    target: Target,
+) -> GeneratorResult<TokenStream> {
+    let defined = events
+        .iter()
+        .map(|def| {
+            let struct_name = format_ident!("{}", def.name);
+            let opts = struct_opts.get(&def.name).cloned().unwrap_or_default();
+            if opts.skip {
+                return Ok(quote! {});
+            }
+            let typedef = typedefs
+                .iter()
+                .find(|d| d.name == def.name)
+                .ok_or_else(|| {
+                    GeneratorError::unsupported(&def.name, "event has no type definition")
+                })?;
             if let anchor_lang_idl_spec::IdlTypeDefTy::Struct { fields } = &typedef.ty {
-                generate_event(typedefs, &struct_name, fields)
+                generate_event(
+                    typedefs,
+                    &struct_name,
+                    &def.discriminator,
+                    &typedef.docs,
+                    fields,
+                    &opts,
+                    target,
+                )
             } else {
-                quote! {}
+                Ok(quote! {})
             }
-        }
-    });
-    quote! {
+        })
+        .collect::<GeneratorResult<Vec<_>>>()?;
+    let event_enum = match target {
+        Target::Anchor => generate_event_enum(events, typedefs, struct_opts),
+        Target::NoAnchor | Target::Pinocchio => quote! {},
+    };
+    Ok(quote! {
         #(#defined)*
+
+        #event_enum
+    })
+}
+
+/// Generates an `Event` enum over all events, with parsers for `emit!` logs and `emit_cpi!` data.
+pub fn generate_event_enum(
+    events: &[IdlEvent],
+    typedefs: &[IdlTypeDef],
+    struct_opts: &BTreeMap<String, StructOpts>,
+) -> TokenStream {
+    let names = events
+        .iter()
+        .filter(|event| {
+            let skip = struct_opts.get(&event.name).is_some_and(|opts| opts.skip);
+            let is_struct = typedefs.iter().any(|def| {
+                def.name == event.name
+                    && matches!(def.ty, anchor_lang_idl_spec::IdlTypeDefTy::Struct { .. })
+            });
+            !skip && is_struct
+        })
+        .map(|event| format_ident!("{}", event.name))
+        .collect::<Vec<_>>();
+    if names.is_empty() {
+        return quote! {};
+    }
+    quote! {
+        /// An event emitted by the program.
+        #[derive(Debug)]
+        #[allow(clippy::large_enum_variant)]
+        pub enum Event {
+            #(#names(#names)),*
+        }
+
+        impl Event {
+            /// Decodes an event from its data, which starts with the discriminator of the event.
+            ///
+            /// Returns [None] if the data is not an event of this program.
+            pub fn try_from_bytes(data: &[u8]) -> Result<Option<Self>> {
+                #(
+                    if let Some(event) = data.strip_prefix(#names::DISCRIMINATOR) {
+                        let event = #names::try_from_slice(event)
+                            .map_err(|_| anchor_lang::error::ErrorCode::InstructionDidNotDeserialize)?;
+                        return Ok(Some(Self::#names(event)));
+                    }
+                )*
+                Ok(None)
+            }
+
+            /// Decodes an event from a `Program data:` log line written by `emit!`.
+            ///
+            /// Returns [None] if the line is not an event of this program.
+            pub fn try_from_log(line: &str) -> Result<Option<Self>> {
+                use anchor_lang::__private::base64::Engine;
+                let encoded = match line.strip_prefix("Program data: ") {
+                    Some(encoded) => encoded,
+                    None => return Ok(None),
+                };
+                let data = anchor_lang::__private::base64::engine::general_purpose::STANDARD
+                    .decode(encoded)
+                    .map_err(|_| anchor_lang::error::ErrorCode::InstructionDidNotDeserialize)?;
+                Self::try_from_bytes(&data)
+            }
+
+            /// Decodes an event from the data of the self-invoked instruction written by `emit_cpi!`.
+            ///
+            /// Returns [None] if the data is not an event of this program.
+            pub fn try_from_cpi_data(data: &[u8]) -> Result<Option<Self>> {
+                match data.strip_prefix(anchor_lang::event::EVENT_IX_TAG_LE) {
+                    Some(data) => Self::try_from_bytes(data),
+                    None => Ok(None),
+                }
+            }
+        }
+
+        /// Decodes the events written by `emit!` in the log messages of a transaction.
+        ///
+        /// Only lines logged while the program is executing are decoded, since other
+        /// programs may log events with the same discriminators.
+        pub fn parse_logs<'a>(logs: impl IntoIterator<Item = &'a str>) -> Result<Vec<Event>> {
+            let program_id = ID.to_string();
+            let mut invocations: Vec<&str> = vec![];
+            let mut events = vec![];
+            for line in logs {
+                // Runtime lines name the program by its address, unlike `Program log:` lines.
+                let mut words = line.split_whitespace();
+                if let (Some("Program"), Some(invoked), Some(status)) =
+                    (words.next(), words.next(), words.next())
+                {
+                    if invoked.parse::<Pubkey>().is_ok() {
+                        let is_depth = |word: Option<&str>| {
+                            word.and_then(|word| word.strip_prefix('['))
+                                .and_then(|word| word.strip_suffix(']'))
+                                .is_some_and(|depth| depth.parse::<usize>().is_ok())
+                        };
+                        if status == "invoke" && is_depth(words.next()) {
+                            invocations.push(invoked);
+                            continue;
+                        }
+                        if status == "success" || status == "failed:" {
+                            invocations.pop();
+                            continue;
+                        }
+                    }
+                }
+                if invocations.last() == Some(&program_id.as_str()) {
+                    if let Some(event) = Event::try_from_log(line)? {
+                        events.push(event);
+                    }
+                }
+            }
+            Ok(events)
+        }
     }
 }
@@ -1,39 +1,68 @@
-use anchor_lang_idl_spec::{IdlDefinedFields, IdlField};
+use anchor_lang_idl_spec::{IdlDefinedFields, IdlField, IdlType};
 use heck::ToSnakeCase;
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
 
+use crate::GeneratorResult;
+
 /// Generates struct fields from a list of [IdlField]s.
-pub fn generate_struct_fields_from_slice(fields: &[IdlField]) -> TokenStream {
-    let fields_rendered = fields.iter().map(|arg| {
-        let name = format_ident!("{}", arg.name.to_snake_case());
-        let type_name = crate::ty_to_rust_type(&arg.ty);
-        let stream: proc_macro2::TokenStream = type_name.parse().unwrap();
-        quote! {
-            pub #name: #stream
-        }
-    });
-    quote! {
+pub fn generate_struct_fields_from_slice(fields: &[IdlField]) -> GeneratorResult<TokenStream> {
+    let fields_rendered = fields
+        .iter()
+        .map(|arg| {
+            let name = format_ident!("{}", arg.name.to_snake_case());
+            let type_name = crate::ty_to_rust_type(&arg.ty)?;
+            let stream: proc_macro2::TokenStream = type_name.parse().unwrap();
+            let docs = crate::generate_docs(&arg.docs);
+            Ok(quote! {
+                #docs
+                pub #name: #stream
+            })
+        })
+        .collect::<GeneratorResult<Vec<_>>>()?;
+    Ok(quote! {
         #(#fields_rendered),*
-    }
+    })
 }
 
-pub fn get_idl_defined_fields_as_slice(fields: &Option<IdlDefinedFields>) -> &[IdlField] {
-    match fields {
-        Some(IdlDefinedFields::Named(fields)) => fields,
-        None => &[],
-        _ => todo!(),
-    }
+/// Generates tuple struct fields from a list of [IdlType]s.
+pub fn generate_tuple_struct_fields(fields: &[IdlType]) -> GeneratorResult<TokenStream> {
+    let fields_rendered = fields
+        .iter()
+        .map(|ty| {
+            let type_name = crate::ty_to_rust_type(ty)?;
+            let stream: proc_macro2::TokenStream = type_name.parse().unwrap();
+            Ok(quote! {
+                pub #stream
+            })
+        })
+        .collect::<GeneratorResult<Vec<_>>>()?;
+    Ok(quote! {
+        #(#fields_rendered),*
+    })
 }
 
-/// Generates struct fields from a list of [IdlField]s.
-pub fn generate_struct_fields(fields: &Option<IdlDefinedFields>) -> TokenStream {
-    if let Some(fields) = fields {
-        match fields {
-            IdlDefinedFields::Named(fields) => generate_struct_fields_from_slice(fields),
-            IdlDefinedFields::Tuple(_) => todo!(),
+/// Generates the body of a struct from its [IdlDefinedFields].
+///
+/// Named fields are wrapped in braces and tuple fields in parentheses.
+pub fn generate_struct_body(fields: &Option<IdlDefinedFields>) -> GeneratorResult<TokenStream> {
+    Ok(match fields {
+        Some(IdlDefinedFields::Named(fields)) => {
+            let fields_rendered = generate_struct_fields_from_slice(fields)?;
+            quote! {
+                {
+                    #fields_rendered
+                }
+            }
+        }
+        Some(IdlDefinedFields::Tuple(fields)) => {
+            let fields_rendered = generate_tuple_struct_fields(fields)?;
+            quote! {
+                (#fields_rendered);
+            }
         }
-    } else {
-        quote! {}
-    }
+        None => quote! {
+            {}
+        },
+    })
 }
@@ -0,0 +1,135 @@
+use std::{fmt, io, path::PathBuf};
+
+use proc_macro2::{Span, TokenStream};
+
+/// An error encountered while generating code from an IDL.
+#[derive(Debug)]
+pub enum GeneratorError {
+    /// `CARGO_MANIFEST_DIR` is not set, so the IDL path cannot be resolved.
+    MissingManifestDir,
+    /// The IDL file could not be read.
+    ReadIdl { path: PathBuf, error: io::Error },
+    /// The IDL file is not a valid IDL.
+    ParseIdl {
+        path: PathBuf,
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// A generator option referenced a type by something other than a plain identifier.
+    InvalidTypePath { option: &'static str, path: String },
+    /// The `repr` of a type is not an integer type.
+    InvalidRepr { ty: String, repr: String },
+    /// The target is not a known framework.
+    UnknownTarget(String),
+    /// Options were given for a type which is not in the IDL.
+    UnknownType(String),
+    /// The config file could not be read.
+    ReadConfig { path: PathBuf, error: io::Error },
+    /// The config file is not a valid config.
+    ParseConfig { path: PathBuf, message: String },
+    /// An item in the IDL cannot be generated.
+    UnsupportedItem { item: String, reason: String },
+    /// `OUT_DIR` is not set, so the generated code cannot be written from a build script.
+    MissingOutDir,
+    /// The generated code is not a valid Rust file.
+    FormatOutput(syn::Error),
+    /// The generated code could not be written.
+    WriteOutput { path: PathBuf, error: io::Error },
+}
+
+impl GeneratorError {
+    /// Creates a [GeneratorError::UnsupportedItem].
+    pub fn unsupported(item: impl Into<String>, reason: impl Into<String>) -> Self {
+        GeneratorError::UnsupportedItem {
+            item: item.into(),
+            reason: reason.into(),
+        }
+    }
+
+    /// Renders this error as a `compile_error!` invocation at the given span.
+    pub fn to_compile_error(&self, span: Span) -> TokenStream {
+        syn::Error::new(span, self).to_compile_error()
+    }
+}
+
+impl fmt::Display for GeneratorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GeneratorError::MissingManifestDir => {
+                write!(
+                    f,
+                    "CARGO_MANIFEST_DIR is not set; cannot resolve the IDL path"
+                )
+            }
+            GeneratorError::ReadIdl { path, error } => {
+                write!(f, "could not read IDL at {}: {}", path.display(), error)
+            }
+            // The message includes the line and column of the error.
+            GeneratorError::ParseIdl { path, message, .. } => {
+                write!(f, "invalid IDL at {}: {}", path.display(), message)
+            }
+            GeneratorError::InvalidTypePath { option, path } => write!(
+                f,
+                "`{}` expects type names, but `{}` is not a single identifier",
+                option, path
+            ),
+            GeneratorError::InvalidRepr { ty, repr } => write!(
+                f,
+                "`repr` of `{}` must be an integer type, but got `{}`",
+                ty, repr
+            ),
+            GeneratorError::UnknownTarget(target) => write!(
+                f,
+                "unknown target `{}`, expected `anchor`, `no-anchor` or `pinocchio`",
+                target
+            ),
+            GeneratorError::UnknownType(ty) => write!(
+                f,
+                "options were given for `{}`, which is not a type or account in the IDL",
+                ty
+            ),
+            GeneratorError::ReadConfig { path, error } => {
+                write!(f, "could not read config at {}: {}", path.display(), error)
+            }
+            GeneratorError::ParseConfig { path, message } => {
+                write!(f, "invalid config at {}: {}", path.display(), message)
+            }
+            GeneratorError::UnsupportedItem { item, reason } => {
+                write!(f, "cannot generate `{}`: {}", item, reason)
+            }
+            GeneratorError::MissingOutDir => {
+                write!(
+                    f,
+                    "OUT_DIR is not set; the builder must be run from a build script"
+                )
+            }
+            GeneratorError::FormatOutput(error) => {
+                write!(f, "generated code is not valid Rust: {}", error)
+            }
+            GeneratorError::WriteOutput { path, error } => {
+                write!(
+                    f,
+                    "could not write generated code to {}: {}",
+                    path.display(),
+                    error
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for GeneratorError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            GeneratorError::ReadIdl { error, .. } => Some(error),
+            GeneratorError::ReadConfig { error, .. } => Some(error),
+            GeneratorError::FormatOutput(error) => Some(error),
+            GeneratorError::WriteOutput { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// A [Result] whose error is a [GeneratorError].
+pub type GeneratorResult<T> = Result<T, GeneratorError>;
@@ -1,54 +1,598 @@
-use anchor_lang_idl_spec::IdlInstruction;
+use std::collections::BTreeMap;
+
+use anchor_lang_idl_spec::{
+    IdlAccount, IdlArrayLen, IdlDefinedFields, IdlGenericArg, IdlInstruction, IdlType, IdlTypeDef,
+    IdlTypeDefGeneric, IdlTypeDefTy,
+};
 use heck::{ToPascalCase, ToSnakeCase};
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
 
-/// Generates a single instruction handler.
-pub fn generate_ix_handler(ix: &IdlInstruction) -> TokenStream {
-    let ix_name = format_ident!("{}", ix.name.to_snake_case());
-    let accounts_name = format_ident!("{}", ix.name.to_pascal_case());
+use crate::{fields::generate_struct_fields_from_slice, GeneratorResult, Target};
 
-    let args = ix
-        .args
+/// Generates the arguments of an instruction as function parameters.
+fn generate_ix_args(ix: &IdlInstruction) -> GeneratorResult<Vec<TokenStream>> {
+    ix.args
         .iter()
         .map(|arg| {
-            let name = format_ident!("_{}", arg.name.to_snake_case());
-            let type_name = crate::ty_to_rust_type(&arg.ty);
+            let name = format_ident!("{}", arg.name.to_snake_case());
+            let type_name = crate::ty_to_rust_type(&arg.ty)?;
             let stream: proc_macro2::TokenStream = type_name.parse().unwrap();
-            quote! {
+            Ok(quote! {
                 #name: #stream
-            }
+            })
         })
-        .collect::<Vec<_>>();
+        .collect()
+}
+
+/// Generates the instruction data struct of a single instruction.
+pub fn generate_ix_data_struct(
+    ix: &IdlInstruction,
+    target: Target,
+) -> GeneratorResult<TokenStream> {
+    let struct_name = format_ident!("{}", ix.name.to_pascal_case());
+    let docs = crate::generate_docs(&ix.docs);
+    let discriminator = crate::generate_discriminator(&ix.discriminator);
 
-    if cfg!(feature = "compat-program-result") {
+    let struct_def = if ix.args.is_empty() {
+        quote! {
+            pub struct #struct_name;
+        }
+    } else {
+        let fields = generate_struct_fields_from_slice(&ix.args)?;
         quote! {
-            pub fn #ix_name(
-                _ctx: Context<#accounts_name>,
-                #(#args),*
-            ) -> ProgramResult {
-                unimplemented!("This program is a wrapper for CPI.")
+            pub struct #struct_name {
+                #fields
             }
         }
+    };
+
+    if target != Target::Anchor {
+        // Pinocchio CPI helpers serialize into a fixed-size buffer instead.
+        let data_fn = if target == Target::NoAnchor {
+            quote! {
+                /// Serializes the instruction data, prefixed by its discriminator.
+                pub fn data(&self) -> Vec<u8> {
+                    let mut data = Self::DISCRIMINATOR.to_vec();
+                    ::borsh::BorshSerialize::serialize(self, &mut data).unwrap();
+                    data
+                }
+            }
+        } else {
+            quote! {}
+        };
+        return Ok(quote! {
+            #docs
+            #[derive(::borsh::BorshSerialize, ::borsh::BorshDeserialize, Debug)]
+            #struct_def
+
+            impl #struct_name {
+                /// Discriminator at the start of the instruction's data.
+                pub const DISCRIMINATOR: &'static [u8] = &#discriminator;
+
+                #data_fn
+            }
+        });
+    }
+
+    Ok(quote! {
+        #docs
+        #[derive(AnchorSerialize, AnchorDeserialize, Debug)]
+        #struct_def
+
+        impl anchor_lang::Discriminator for #struct_name {
+            const DISCRIMINATOR: &'static [u8] = &#discriminator;
+        }
+
+        impl anchor_lang::InstructionData for #struct_name {}
+
+        impl anchor_lang::Owner for #struct_name {
+            fn owner() -> Pubkey {
+                ID
+            }
+        }
+    })
+}
+
+/// Generates a CPI helper for a single instruction.
+pub fn generate_ix_cpi_fn(ix: &IdlInstruction) -> GeneratorResult<TokenStream> {
+    let ix_name = format_ident!("{}", ix.name.to_snake_case());
+    let struct_name = format_ident!("{}", ix.name.to_pascal_case());
+    let docs = crate::generate_docs(&ix.docs);
+    let args = generate_ix_args(ix)?;
+    let ix_data = generate_ix_data(ix);
+    let accounts_ty = if ix.accounts.is_empty() {
+        quote! { accounts::#struct_name }
     } else {
+        quote! { accounts::#struct_name<'info> }
+    };
+
+    let error_ty = if cfg!(feature = "compat-program-result") {
+        quote! { ProgramError }
+    } else {
+        quote! { anchor_lang::error::Error }
+    };
+    let (ret, value) = match &ix.returns {
+        Some(returns) => {
+            let returns: TokenStream = crate::ty_to_rust_type(returns)?.parse().unwrap();
+            (
+                quote! { Return<#returns> },
+                quote! {
+                    Return {
+                        program_id: ix.program_id,
+                        phantom: std::marker::PhantomData,
+                    }
+                },
+            )
+        }
+        None => (quote! { () }, quote! { () }),
+    };
+
+    Ok(quote! {
+        #docs
+        pub fn #ix_name<'info>(
+            ctx: CpiContext<'_, '_, '_, 'info, #accounts_ty>,
+            #(#args),*
+        ) -> std::result::Result<#ret, #error_ty> {
+            let ix = anchor_lang::solana_program::instruction::Instruction {
+                program_id: ctx.program.key(),
+                accounts: ctx.to_account_metas(None),
+                data: anchor_lang::InstructionData::data(&#ix_data),
+            };
+            anchor_lang::solana_program::program::invoke_signed(
+                &ix,
+                &ctx.to_account_infos(),
+                ctx.signer_seeds,
+            )?;
+            Ok(#value)
+        }
+    })
+}
+
+/// Generates the instruction data expression of an instruction from its arguments.
+fn generate_ix_data(ix: &IdlInstruction) -> TokenStream {
+    let struct_name = format_ident!("{}", ix.name.to_pascal_case());
+    let arg_names = ix
+        .args
+        .iter()
+        .map(|arg| format_ident!("{}", arg.name.to_snake_case()));
+    if ix.args.is_empty() {
+        quote! { instruction::#struct_name }
+    } else {
+        quote! { instruction::#struct_name { #(#arg_names),* } }
+    }
+}
+
+/// Generates a builder of the [solana_program::instruction::Instruction] of a single instruction.
+pub fn generate_ix_builder_fn(ix: &IdlInstruction) -> GeneratorResult<TokenStream> {
+    let ix_name = format_ident!("{}", ix.name.to_snake_case());
+    let struct_name = format_ident!("{}", ix.name.to_pascal_case());
+    let docs = crate::generate_docs(&ix.docs);
+    let args = generate_ix_args(ix)?;
+    let ix_data = generate_ix_data(ix);
+    Ok(quote! {
+        #docs
+        pub fn #ix_name(
+            accounts: &crate::accounts::#struct_name,
+            #(#args),*
+        ) -> solana_program::instruction::Instruction {
+            solana_program::instruction::Instruction {
+                program_id: ID,
+                accounts: accounts.to_account_metas(),
+                data: crate::#ix_data.data(),
+            }
+        }
+    })
+}
+
+/// Generates a CPI helper which invokes a single instruction with `invoke_signed`,
+/// without Anchor.
+pub fn generate_ix_invoke_fn(ix: &IdlInstruction) -> GeneratorResult<TokenStream> {
+    let ix_name = format_ident!("{}", ix.name.to_snake_case());
+    let struct_name = format_ident!("{}", ix.name.to_pascal_case());
+    let docs = crate::generate_docs(&ix.docs);
+    let args = generate_ix_args(ix)?;
+    let arg_names = ix
+        .args
+        .iter()
+        .map(|arg| format_ident!("{}", arg.name.to_snake_case()));
+    Ok(quote! {
+        #docs
+        pub fn #ix_name(
+            accounts: &crate::accounts::#struct_name,
+            account_infos: &[AccountInfo],
+            #(#args,)*
+            signer_seeds: &[&[&[u8]]],
+        ) -> ProgramResult {
+            solana_program::program::invoke_signed(
+                &crate::instruction::#ix_name(accounts, #(#arg_names),*),
+                account_infos,
+                signer_seeds,
+            )
+        }
+    })
+}
+
+/// A generic argument of a type definition, resolved to what its length depends on.
+#[derive(Clone, Copy)]
+enum GenericLen {
+    /// Maximum serialized length of a type argument.
+    Type(usize),
+    /// Value of a const argument.
+    Const(usize),
+}
+
+/// Gets the maximum length of a Borsh serialized value of a type.
+fn max_serialized_len(defs: &[IdlTypeDef], ty: &IdlType) -> Option<usize> {
+    max_len(defs, ty, &BTreeMap::new())
+}
+
+fn max_len(
+    defs: &[IdlTypeDef],
+    ty: &IdlType,
+    generics: &BTreeMap<String, GenericLen>,
+) -> Option<usize> {
+    Some(match ty {
+        IdlType::Bool | IdlType::U8 | IdlType::I8 => 1,
+        IdlType::U16 | IdlType::I16 => 2,
+        IdlType::U32 | IdlType::I32 | IdlType::F32 => 4,
+        IdlType::U64 | IdlType::I64 | IdlType::F64 => 8,
+        IdlType::U128 | IdlType::I128 => 16,
+        IdlType::U256 | IdlType::I256 | IdlType::Pubkey => 32,
+        IdlType::Option(inner) => 1 + max_len(defs, inner, generics)?,
+        IdlType::Array(inner, len) => {
+            let len = match len {
+                IdlArrayLen::Value(len) => *len,
+                IdlArrayLen::Generic(name) => match generics.get(name)? {
+                    GenericLen::Const(len) => *len,
+                    GenericLen::Type(_) => return None,
+                },
+            };
+            max_len(defs, inner, generics)? * len
+        }
+        IdlType::Generic(name) => match generics.get(name)? {
+            GenericLen::Type(len) => *len,
+            GenericLen::Const(_) => return None,
+        },
+        IdlType::Defined {
+            name,
+            generics: args,
+        } => {
+            let def = defs.iter().find(|def| &def.name == name)?;
+            if def.generics.len() != args.len() {
+                return None;
+            }
+            // The arguments are resolved in the scope of the type which uses the definition.
+            let def_generics = def
+                .generics
+                .iter()
+                .zip(args)
+                .map(|(param, arg)| match (param, arg) {
+                    (IdlTypeDefGeneric::Type { name }, IdlGenericArg::Type { ty }) => {
+                        Some((name.clone(), GenericLen::Type(max_len(defs, ty, generics)?)))
+                    }
+                    (IdlTypeDefGeneric::Const { name, .. }, IdlGenericArg::Const { value }) => {
+                        let value = match value.parse() {
+                            Ok(value) => value,
+                            Err(_) => match generics.get(value)? {
+                                GenericLen::Const(value) => *value,
+                                GenericLen::Type(_) => return None,
+                            },
+                        };
+                        Some((name.clone(), GenericLen::Const(value)))
+                    }
+                    _ => None,
+                })
+                .collect::<Option<BTreeMap<_, _>>>()?;
+            match &def.ty {
+                IdlTypeDefTy::Struct { fields } => max_fields_len(defs, fields, &def_generics)?,
+                // Enums are serialized as the index of the variant followed by its fields.
+                IdlTypeDefTy::Enum { variants } => {
+                    let mut max = 0;
+                    for variant in variants {
+                        max = max.max(max_fields_len(defs, &variant.fields, &def_generics)?);
+                    }
+                    1 + max
+                }
+                IdlTypeDefTy::Type { alias } => max_len(defs, alias, &def_generics)?,
+            }
+        }
+        _ => return None,
+    })
+}
+
+fn max_fields_len(
+    defs: &[IdlTypeDef],
+    fields: &Option<IdlDefinedFields>,
+    generics: &BTreeMap<String, GenericLen>,
+) -> Option<usize> {
+    match fields {
+        Some(IdlDefinedFields::Named(fields)) => fields
+            .iter()
+            .map(|field| max_len(defs, &field.ty, generics))
+            .sum(),
+        Some(IdlDefinedFields::Tuple(fields)) => {
+            fields.iter().map(|ty| max_len(defs, ty, generics)).sum()
+        }
+        None => Some(0),
+    }
+}
+
+/// Generates a zero-allocation CPI helper for a single instruction using Pinocchio.
+///
+/// The instruction data is serialized into a buffer of its maximum length. If the
+/// arguments have no maximum length, such as strings and vectors, the helper takes
+/// the buffer as a parameter instead.
+pub fn generate_ix_pinocchio_fn(
+    defs: &[IdlTypeDef],
+    ix: &IdlInstruction,
+) -> GeneratorResult<TokenStream> {
+    let ix_name = format_ident!("{}", ix.name.to_snake_case());
+    let struct_name = format_ident!("{}", ix.name.to_pascal_case());
+    let args = generate_ix_args(ix)?;
+
+    let mut accounts = vec![];
+    crate::flatten_account_fields(
+        &ix.name.to_pascal_case(),
+        &ix.accounts,
+        &quote! { accounts },
+        &mut accounts,
+    );
+    let num_accounts = accounts.len();
+    // Missing optional accounts are replaced by the program.
+    let has_optional = accounts.iter().any(|(_, info)| info.optional);
+    let account_metas = accounts.iter().map(|(path, info)| {
+        let writable = info.writable;
+        let signer = info.signer;
+        if info.optional {
+            quote! {
+                match #path {
+                    Some(account) => AccountMeta::new(account.key(), #writable, #signer),
+                    None => AccountMeta::readonly(program.key()),
+                }
+            }
+        } else {
+            quote! { AccountMeta::new(#path.key(), #writable, #signer) }
+        }
+    });
+    let account_infos = accounts.iter().map(|(path, info)| {
+        if info.optional {
+            quote! { #path.unwrap_or(program) }
+        } else {
+            quote! { #path }
+        }
+    });
+
+    let (lifetime, accounts_ty) = if ix.accounts.is_empty() {
+        (quote! {}, quote! { accounts::#struct_name })
+    } else {
+        (quote! { <'info> }, quote! { accounts::#struct_name<'info> })
+    };
+    let program_param = if has_optional {
+        quote! { program: &'info AccountInfo, }
+    } else {
+        quote! {}
+    };
+    let accounts_unused = if accounts.is_empty() {
+        quote! {
+            let _ = accounts;
+        }
+    } else {
+        quote! {}
+    };
+
+    let data_len = ix
+        .args
+        .iter()
+        .map(|arg| max_serialized_len(defs, &arg.ty))
+        .sum::<Option<usize>>()
+        .map(|len| ix.discriminator.len() + len);
+    let mut docs = ix.docs.clone();
+    let (buffer_param, write_data, data) = if ix.args.is_empty() {
+        (
+            quote! {},
+            quote! {},
+            quote! { crate::instruction::#struct_name::DISCRIMINATOR },
+        )
+    } else {
+        let (buffer_param, buffer) = match data_len {
+            Some(data_len) => (
+                quote! {},
+                quote! {
+                    let mut data = [0u8; #data_len];
+                    let buffer = &mut data[..];
+                },
+            ),
+            None => {
+                if !docs.is_empty() {
+                    docs.push(String::new());
+                }
+                docs.push(
+                    "The instruction data is serialized into `buffer`, since the arguments have no maximum length."
+                        .to_string(),
+                );
+                (quote! { buffer: &mut [u8], }, quote! {})
+            }
+        };
+        let discriminator_len = ix.discriminator.len();
+        let ix_data = generate_ix_data(ix);
+        (
+            buffer_param,
+            // The arguments are moved first, so that they are not shadowed by the locals.
+            quote! {
+                let args = crate::#ix_data;
+                #buffer
+                let buffer_len = buffer.len();
+                buffer
+                    .get_mut(..#discriminator_len)
+                    .ok_or(ProgramError::InvalidInstructionData)?
+                    .copy_from_slice(crate::instruction::#struct_name::DISCRIMINATOR);
+                let mut buf = &mut buffer[#discriminator_len..];
+                ::borsh::BorshSerialize::serialize(&args, &mut buf)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                let len = buffer_len - buf.len();
+            },
+            quote! { &buffer[..len] },
+        )
+    };
+    let docs = crate::generate_docs(&docs);
+
+    Ok(quote! {
+        #docs
+        pub fn #ix_name #lifetime(
+            accounts: &#accounts_ty,
+            #program_param
+            #(#args,)*
+            #buffer_param
+            signers: &[Signer],
+        ) -> ProgramResult {
+            #write_data
+            #accounts_unused
+            let account_metas: [AccountMeta; #num_accounts] = [#(#account_metas),*];
+            let account_infos: [&AccountInfo; #num_accounts] = [#(#account_infos),*];
+            pinocchio::cpi::invoke_signed(
+                &Instruction {
+                    program_id: &crate::ID,
+                    accounts: &account_metas,
+                    data: #data,
+                },
+                &account_infos,
+                signers,
+            )
+        }
+    })
+}
+
+/// Generates the [Return] type of CPI helpers for instructions which return a value.
+pub fn generate_ix_cpi_return() -> TokenStream {
+    quote! {
+        /// The value returned by an instruction, which is read from the return data.
+        pub struct Return<T> {
+            program_id: Pubkey,
+            phantom: std::marker::PhantomData<T>,
+        }
+
+        impl<T: AnchorDeserialize> Return<T> {
+            /// Decodes the return data, which must have been set by the invoked program.
+            pub fn get(&self) -> Result<T> {
+                let (program_id, data) = anchor_lang::solana_program::program::get_return_data()
+                    .ok_or(anchor_lang::error::ErrorCode::InstructionDidNotDeserialize)?;
+                if program_id != self.program_id {
+                    return Err(anchor_lang::error::Error::from(
+                        anchor_lang::error::ErrorCode::InvalidProgramId,
+                    )
+                    .with_pubkeys((program_id, self.program_id)));
+                }
+                T::try_from_slice(&data)
+                    .map_err(|_| anchor_lang::error::ErrorCode::InstructionDidNotDeserialize.into())
+            }
+        }
+    }
+}
+
+/// Generates re-exports of the CPI account structs generated by `#[derive(Accounts)]`.
+pub fn generate_ix_cpi_client_accounts(ixs: &[IdlInstruction]) -> TokenStream {
+    let re_exports = ixs.iter().map(|ix| {
+        let module = format_ident!("__cpi_client_accounts_{}", ix.name.to_snake_case());
         quote! {
-            pub fn #ix_name(
-                _ctx: Context<#accounts_name>,
-                #(#args),*
-            ) -> Result<()> {
-                unimplemented!("This program is a wrapper for CPI.")
+            pub use crate::ix_accounts::#module::*;
+        }
+    });
+    quote! {
+        #(#re_exports)*
+    }
+}
+
+/// Generates the off-chain account structs of all instructions.
+pub fn generate_ix_client_structs(
+    defs: &[IdlTypeDef],
+    account_defs: &[IdlAccount],
+    ixs: &[IdlInstruction],
+    target: Target,
+) -> TokenStream {
+    let defs = ixs.iter().map(|ix| {
+        crate::generate_client_accounts_struct(
+            defs,
+            account_defs,
+            &ix.name.to_pascal_case(),
+            &ix.docs,
+            &ix.accounts,
+            target,
+        )
+    });
+    let (result, error) = if target == Target::Anchor {
+        (
+            quote! { Result<Pubkey> },
+            quote! { anchor_lang::error::ErrorCode::AccountNotEnoughKeys },
+        )
+    } else {
+        (
+            quote! { std::result::Result<Pubkey, ProgramError> },
+            quote! { ProgramError::NotEnoughAccountKeys },
+        )
+    };
+    quote! {
+        #(#defs)*
+
+        /// Removes the first key of an instruction's account keys.
+        fn next_account_key(keys: &mut &[Pubkey]) -> #result {
+            let (key, rest) = keys.split_first().ok_or(#error)?;
+            *keys = rest;
+            Ok(*key)
+        }
+    }
+}
+
+/// Generates an `Instruction` enum over all instructions, which decodes raw instructions.
+pub fn generate_ix_enum(ixs: &[IdlInstruction]) -> TokenStream {
+    if ixs.is_empty() {
+        return quote! {};
+    }
+    let names = ixs
+        .iter()
+        .map(|ix| format_ident!("{}", ix.name.to_pascal_case()))
+        .collect::<Vec<_>>();
+    quote! {
+        /// An instruction of the program, decoded from its data and account keys.
+        #[derive(Debug)]
+        #[allow(clippy::large_enum_variant)]
+        pub enum Instruction {
+            #(
+                #names {
+                    args: #names,
+                    accounts: crate::accounts::#names,
+                }
+            ),*
+        }
+
+        impl Instruction {
+            /// Decodes an instruction from its data and the keys of its accounts.
+            ///
+            /// Returns [None] if the data does not start with the discriminator of an
+            /// instruction of this program. Remaining accounts are ignored, and so are
+            /// trailing bytes of the data, as in the program.
+            pub fn decode(data: &[u8], accounts: &[Pubkey]) -> Result<Option<Self>> {
+                #(
+                    if let Some(args) = data.strip_prefix(#names::DISCRIMINATOR) {
+                        let args: #names = anchor_lang::AnchorDeserialize::deserialize(&mut &args[..])
+                            .map_err(|_| anchor_lang::error::ErrorCode::InstructionDidNotDeserialize)?;
+                        let accounts = crate::accounts::#names::from_account_keys(&mut &accounts[..])?;
+                        return Ok(Some(Self::#names { args, accounts }));
+                    }
+                )*
+                Ok(None)
             }
         }
     }
 }
 
 /// Generates instruction context structs.
-pub fn generate_ix_structs(ixs: &[IdlInstruction]) -> TokenStream {
+pub fn generate_ix_structs(ixs: &[IdlInstruction], target: Target) -> TokenStream {
     let defs = ixs.iter().map(|ix| {
         let accounts_name = format_ident!("{}", ix.name.to_pascal_case());
 
         let (all_structs, all_fields) =
-            crate::generate_account_fields(&ix.name.to_pascal_case(), &ix.accounts);
+            crate::generate_account_fields(&ix.name.to_pascal_case(), &ix.accounts, target);
 
         let struct_def = if ix.accounts.is_empty() {
             quote! {
@@ -64,10 +608,17 @@ pub fn generate_ix_structs(ixs: &[IdlInstruction]) -> TokenStream {
             }
         };
 
+        let derive = if target == Target::Pinocchio {
+            quote! { #[derive(Clone, Copy)] }
+        } else {
+            quote! { #[derive(Accounts)] }
+        };
+        let docs = crate::generate_docs(&ix.docs);
         quote! {
             #all_structs
 
-            #[derive(Accounts)]
+            #docs
+            #derive
             #struct_def
         }
     });
@@ -76,10 +627,63 @@ pub fn generate_ix_structs(ixs: &[IdlInstruction]) -> TokenStream {
     }
 }
 
-/// Generates all instruction handlers.
-pub fn generate_ix_handlers(ixs: &[IdlInstruction]) -> TokenStream {
-    let streams = ixs.iter().map(generate_ix_handler);
-    quote! {
+/// Generates all instruction data structs.
+pub fn generate_ix_data_structs(
+    ixs: &[IdlInstruction],
+    target: Target,
+) -> GeneratorResult<TokenStream> {
+    let streams = ixs
+        .iter()
+        .map(|ix| generate_ix_data_struct(ix, target))
+        .collect::<GeneratorResult<Vec<_>>>()?;
+    Ok(quote! {
         #(#streams)*
-    }
+    })
+}
+
+/// Generates all CPI helpers.
+pub fn generate_ix_cpi_fns(ixs: &[IdlInstruction]) -> GeneratorResult<TokenStream> {
+    let streams = ixs
+        .iter()
+        .map(generate_ix_cpi_fn)
+        .collect::<GeneratorResult<Vec<_>>>()?;
+    Ok(quote! {
+        #(#streams)*
+    })
+}
+
+/// Generates all instruction builders.
+pub fn generate_ix_builder_fns(ixs: &[IdlInstruction]) -> GeneratorResult<TokenStream> {
+    let streams = ixs
+        .iter()
+        .map(generate_ix_builder_fn)
+        .collect::<GeneratorResult<Vec<_>>>()?;
+    Ok(quote! {
+        #(#streams)*
+    })
+}
+
+/// Generates all CPI helpers which do not use Anchor.
+pub fn generate_ix_invoke_fns(ixs: &[IdlInstruction]) -> GeneratorResult<TokenStream> {
+    let streams = ixs
+        .iter()
+        .map(generate_ix_invoke_fn)
+        .collect::<GeneratorResult<Vec<_>>>()?;
+    Ok(quote! {
+        #(#streams)*
+    })
+}
+
+/// Generates all CPI helpers which use Pinocchio.
+pub fn generate_ix_pinocchio_fns(
+    defs: &[IdlTypeDef],
+    ixs: &[IdlInstruction],
+) -> GeneratorResult<TokenStream> {
+    let streams = ixs
+        .iter()
+        .map(|ix| generate_ix_pinocchio_fn(defs, ix))
+        .collect::<GeneratorResult<Vec<_>>>()?;
+    Ok(quote! {
+        #(#streams)*
+    })
 }
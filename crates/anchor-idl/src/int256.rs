@@ -0,0 +1,145 @@
+use anchor_lang_idl_spec::{Idl, IdlDefinedFields, IdlGenericArg, IdlType, IdlTypeDefTy};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::Target;
+
+/// Returns true if the type is or contains a 256-bit integer.
+fn type_uses_int256(ty: &IdlType) -> bool {
+    match ty {
+        IdlType::U256 | IdlType::I256 => true,
+        IdlType::Option(inner) | IdlType::Vec(inner) => type_uses_int256(inner),
+        IdlType::Array(inner, _) => type_uses_int256(inner),
+        IdlType::Defined { generics, .. } => generics.iter().any(|arg| match arg {
+            IdlGenericArg::Type { ty } => type_uses_int256(ty),
+            IdlGenericArg::Const { .. } => false,
+        }),
+        _ => false,
+    }
+}
+
+fn fields_use_int256(fields: &Option<IdlDefinedFields>) -> bool {
+    match fields {
+        Some(IdlDefinedFields::Named(fields)) => fields.iter().any(|f| type_uses_int256(&f.ty)),
+        Some(IdlDefinedFields::Tuple(fields)) => fields.iter().any(type_uses_int256),
+        None => false,
+    }
+}
+
+/// Returns true if any type, instruction or constant in the IDL uses a 256-bit integer.
+pub fn idl_uses_int256(idl: &Idl) -> bool {
+    let in_types = idl.types.iter().any(|def| match &def.ty {
+        IdlTypeDefTy::Struct { fields } => fields_use_int256(fields),
+        IdlTypeDefTy::Enum { variants } => variants.iter().any(|v| fields_use_int256(&v.fields)),
+        IdlTypeDefTy::Type { alias } => type_uses_int256(alias),
+    });
+    let in_ixs = idl.instructions.iter().any(|ix| {
+        ix.args.iter().any(|arg| type_uses_int256(&arg.ty))
+            || ix.returns.as_ref().is_some_and(type_uses_int256)
+    });
+    let in_constants = idl.constants.iter().any(|c| type_uses_int256(&c.ty));
+    in_types || in_ixs || in_constants
+}
+
+/// Parses a decimal integer literal into little-endian 64-bit limbs.
+///
+/// Negative values are stored in two's complement. Returns [None] if the
+/// value does not fit in 256 bits.
+pub fn parse_int256_limbs(digits: &str, negative: bool) -> Option<[u64; 4]> {
+    let mut limbs = [0u64; 4];
+    for c in digits.chars() {
+        let digit = c.to_digit(10)? as u128;
+        let mut carry = digit;
+        for limb in limbs.iter_mut() {
+            let value = (*limb as u128) * 10 + carry;
+            *limb = value as u64;
+            carry = value >> 64;
+        }
+        if carry != 0 {
+            return None;
+        }
+    }
+    if negative {
+        let mut carry = 1u128;
+        for limb in limbs.iter_mut() {
+            let value = (!*limb) as u128 + carry;
+            *limb = value as u64;
+            carry = value >> 64;
+        }
+    }
+    Some(limbs)
+}
+
+/// Generates the `U256` and `I256` types used for 256-bit IDL integers.
+///
+/// Both are stored as four little-endian `u64` limbs, which matches the Borsh
+/// encoding of a 256-bit integer and the layout of `uint`-style types.
+pub fn generate_int256_types(idl: &Idl, target: Target) -> TokenStream {
+    if !idl_uses_int256(idl) {
+        return quote! {};
+    }
+    let borsh_derives = target.borsh_derives();
+    let bytemuck = target.bytemuck();
+    quote! {
+        /// A 256-bit unsigned integer, stored as little-endian 64-bit limbs.
+        #[derive(#borsh_derives, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+        #[repr(C)]
+        pub struct U256(pub [u64; 4]);
+
+        unsafe impl #bytemuck::Pod for U256 {}
+        unsafe impl #bytemuck::Zeroable for U256 {}
+
+        impl U256 {
+            /// Creates a [U256] from its little-endian byte representation.
+            pub fn from_le_bytes(bytes: [u8; 32]) -> Self {
+                let mut limbs = [0u64; 4];
+                for (limb, chunk) in limbs.iter_mut().zip(bytes.chunks_exact(8)) {
+                    *limb = u64::from_le_bytes(chunk.try_into().unwrap());
+                }
+                Self(limbs)
+            }
+
+            /// Returns the little-endian byte representation of this [U256].
+            pub fn to_le_bytes(self) -> [u8; 32] {
+                let mut bytes = [0u8; 32];
+                for (chunk, limb) in bytes.chunks_exact_mut(8).zip(self.0) {
+                    chunk.copy_from_slice(&limb.to_le_bytes());
+                }
+                bytes
+            }
+        }
+
+        impl From<u128> for U256 {
+            fn from(value: u128) -> Self {
+                Self([value as u64, (value >> 64) as u64, 0, 0])
+            }
+        }
+
+        /// A 256-bit signed integer, stored in two's complement as little-endian 64-bit limbs.
+        #[derive(#borsh_derives, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+        #[repr(C)]
+        pub struct I256(pub [u64; 4]);
+
+        unsafe impl #bytemuck::Pod for I256 {}
+        unsafe impl #bytemuck::Zeroable for I256 {}
+
+        impl I256 {
+            /// Creates an [I256] from its little-endian byte representation.
+            pub fn from_le_bytes(bytes: [u8; 32]) -> Self {
+                Self(U256::from_le_bytes(bytes).0)
+            }
+
+            /// Returns the little-endian byte representation of this [I256].
+            pub fn to_le_bytes(self) -> [u8; 32] {
+                U256(self.0).to_le_bytes()
+            }
+        }
+
+        impl From<i128> for I256 {
+            fn from(value: i128) -> Self {
+                let sign = if value < 0 { u64::MAX } else { 0 };
+                Self([value as u64, (value >> 64) as u64, sign, sign])
+            }
+        }
+    }
+}
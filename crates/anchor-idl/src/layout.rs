@@ -0,0 +1,346 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use anchor_lang_idl_spec::{
+    Idl, IdlArrayLen, IdlDefinedFields, IdlGenericArg, IdlRepr, IdlSerialization, IdlType,
+    IdlTypeDef, IdlTypeDefTy,
+};
+use heck::ToSnakeCase;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+use crate::{is_fieldless_enum, GeneratorConfig, StructOpts};
+
+/// Memory layout of a generated type.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Layout {
+    pub zero_copy: bool,
+    pub packed: bool,
+    /// Minimum alignment of the type, from `repr(align(N))`.
+    pub align: Option<usize>,
+}
+
+/// Infers the layout of a type from its `serialization` and `repr` in the IDL.
+///
+/// `repr(transparent)` structs have a single field, so they are laid out like `repr(C)`.
+pub fn infer_layout(def: &IdlTypeDef) -> Layout {
+    let zero_copy = matches!(
+        def.serialization,
+        IdlSerialization::Bytemuck | IdlSerialization::BytemuckUnsafe
+    );
+    let (packed, align) = match &def.repr {
+        Some(IdlRepr::C(modifier)) | Some(IdlRepr::Rust(modifier)) => {
+            (modifier.packed, modifier.align)
+        }
+        _ => (false, None),
+    };
+    Layout {
+        zero_copy,
+        packed,
+        align,
+    }
+}
+
+fn collect_type_refs<'a>(ty: &'a IdlType, out: &mut Vec<&'a str>) {
+    match ty {
+        IdlType::Defined { name, generics } => {
+            out.push(name);
+            for arg in generics {
+                if let IdlGenericArg::Type { ty } = arg {
+                    collect_type_refs(ty, out);
+                }
+            }
+        }
+        IdlType::Option(inner) | IdlType::Vec(inner) | IdlType::Array(inner, _) => {
+            collect_type_refs(inner, out)
+        }
+        _ => {}
+    }
+}
+
+fn collect_fields_refs<'a>(fields: &'a Option<IdlDefinedFields>, out: &mut Vec<&'a str>) {
+    match fields {
+        Some(IdlDefinedFields::Named(fields)) => fields
+            .iter()
+            .for_each(|field| collect_type_refs(&field.ty, out)),
+        Some(IdlDefinedFields::Tuple(fields)) => {
+            fields.iter().for_each(|ty| collect_type_refs(ty, out))
+        }
+        None => {}
+    }
+}
+
+/// Gets the names of the types referenced by a type definition.
+fn type_refs(def: &IdlTypeDef) -> Vec<&str> {
+    let mut refs = vec![];
+    match &def.ty {
+        IdlTypeDefTy::Struct { fields } => collect_fields_refs(fields, &mut refs),
+        IdlTypeDefTy::Enum { variants } => variants
+            .iter()
+            .for_each(|variant| collect_fields_refs(&variant.fields, &mut refs)),
+        IdlTypeDefTy::Type { alias } => collect_type_refs(alias, &mut refs),
+    }
+    refs
+}
+
+/// Resolves the layout of every type in the IDL.
+///
+/// Layouts are inferred from the IDL and overridden by the config. Types referenced
+/// by zero copy types must be zero copy too, unless the config says otherwise.
+pub fn resolve_layouts(idl: &Idl, config: &GeneratorConfig) -> BTreeMap<String, Layout> {
+    let mut layouts = idl
+        .types
+        .iter()
+        .map(|def| {
+            let inferred = infer_layout(def);
+            let type_config = config.type_config(&def.name);
+            let layout = Layout {
+                zero_copy: type_config.zero_copy.unwrap_or(inferred.zero_copy),
+                packed: type_config.packed.unwrap_or(inferred.packed),
+                align: inferred.align,
+            };
+            (def.name.clone(), layout)
+        })
+        .collect::<BTreeMap<_, _>>();
+
+    let mut pending = layouts
+        .iter()
+        .filter(|(_, layout)| layout.zero_copy)
+        .map(|(name, _)| name.clone())
+        .collect::<Vec<_>>();
+    let mut visited = pending.iter().cloned().collect::<BTreeSet<_>>();
+    while let Some(name) = pending.pop() {
+        let Some(def) = idl.types.iter().find(|def| def.name == name) else {
+            continue;
+        };
+        for referenced in type_refs(def) {
+            if !visited.insert(referenced.to_string()) {
+                continue;
+            }
+            if config.type_config(referenced).zero_copy == Some(false) {
+                continue;
+            }
+            if let Some(layout) = layouts.get_mut(referenced) {
+                layout.zero_copy = true;
+                pending.push(referenced.to_string());
+            }
+        }
+    }
+    layouts
+}
+
+/// Size and alignment of a type in bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct TypeLayout {
+    size: usize,
+    align: usize,
+    /// Whether the layout depends on the alignment of 128-bit integers,
+    /// which differs between the host and the chain.
+    uses_int128: bool,
+}
+
+impl TypeLayout {
+    fn primitive(size: usize) -> Self {
+        TypeLayout {
+            size,
+            align: size,
+            uses_int128: false,
+        }
+    }
+}
+
+fn align_up(offset: usize, align: usize) -> usize {
+    offset.div_ceil(align) * align
+}
+
+/// Computes the `repr(C)` layout of an IDL type, as it is laid out on chain.
+///
+/// Returns [None] for types without a fixed layout, such as vectors or enums with fields.
+fn type_layout(
+    defs: &[IdlTypeDef],
+    struct_opts: &BTreeMap<String, StructOpts>,
+    ty: &IdlType,
+) -> Option<TypeLayout> {
+    Some(match ty {
+        IdlType::Bool | IdlType::U8 | IdlType::I8 => TypeLayout::primitive(1),
+        IdlType::U16 | IdlType::I16 => TypeLayout::primitive(2),
+        IdlType::U32 | IdlType::I32 | IdlType::F32 => TypeLayout::primitive(4),
+        IdlType::U64 | IdlType::I64 | IdlType::F64 => TypeLayout::primitive(8),
+        // 128-bit integers are 8-byte aligned on chain.
+        IdlType::U128 | IdlType::I128 => TypeLayout {
+            size: 16,
+            align: 8,
+            uses_int128: true,
+        },
+        IdlType::U256 | IdlType::I256 => TypeLayout {
+            size: 32,
+            align: 8,
+            uses_int128: false,
+        },
+        IdlType::Pubkey => TypeLayout {
+            size: 32,
+            align: 1,
+            uses_int128: false,
+        },
+        IdlType::Array(inner, IdlArrayLen::Value(len)) => {
+            let inner = type_layout(defs, struct_opts, inner)?;
+            TypeLayout {
+                size: inner.size * len,
+                ..inner
+            }
+        }
+        IdlType::Defined { name, generics } if generics.is_empty() => {
+            let def = defs.iter().find(|def| &def.name == name)?;
+            def_layout(defs, struct_opts, def)?.layout
+        }
+        _ => return None,
+    })
+}
+
+/// A field of a struct and its offset.
+struct FieldOffset {
+    name: String,
+    member: TokenStream,
+    offset: usize,
+}
+
+struct DefLayout {
+    layout: TypeLayout,
+    fields: Vec<FieldOffset>,
+}
+
+fn def_layout(
+    defs: &[IdlTypeDef],
+    struct_opts: &BTreeMap<String, StructOpts>,
+    def: &IdlTypeDef,
+) -> Option<DefLayout> {
+    if !def.generics.is_empty() {
+        return None;
+    }
+    let opts = struct_opts.get(&def.name).cloned().unwrap_or_default();
+    match &def.ty {
+        IdlTypeDefTy::Struct { fields } => {
+            let fields: Vec<(String, TokenStream, &IdlType)> = match fields {
+                Some(IdlDefinedFields::Named(fields)) => fields
+                    .iter()
+                    .map(|field| {
+                        let member = format_ident!("{}", field.name.to_snake_case());
+                        (field.name.clone(), quote! { #member }, &field.ty)
+                    })
+                    .collect(),
+                Some(IdlDefinedFields::Tuple(fields)) => fields
+                    .iter()
+                    .enumerate()
+                    .map(|(i, ty)| {
+                        let member = syn::Index::from(i);
+                        (i.to_string(), quote! { #member }, ty)
+                    })
+                    .collect(),
+                None => vec![],
+            };
+            let mut offset = 0;
+            let mut align = opts.align.unwrap_or(1);
+            let mut uses_int128 = false;
+            let mut offsets = vec![];
+            for (name, member, ty) in fields {
+                let field = type_layout(defs, struct_opts, ty)?;
+                uses_int128 |= field.uses_int128;
+                if !opts.packed {
+                    offset = align_up(offset, field.align);
+                    align = align.max(field.align);
+                }
+                offsets.push(FieldOffset {
+                    name,
+                    member,
+                    offset,
+                });
+                offset += field.size;
+            }
+            Some(DefLayout {
+                layout: TypeLayout {
+                    size: align_up(offset, align),
+                    align,
+                    uses_int128,
+                },
+                fields: offsets,
+            })
+        }
+        IdlTypeDefTy::Enum { .. } if is_fieldless_enum(def) => {
+            if !opts.zero_copy && opts.repr.is_none() {
+                return None;
+            }
+            // Zero copy structs store zero copy enums as their `Pod{Name}` wrapper,
+            // which is `repr(transparent)` over the integer `repr` of the enum.
+            let size = match opts.repr.as_deref().unwrap_or("u8") {
+                "u8" | "i8" => 1,
+                "u16" | "i16" => 2,
+                "u32" | "i32" => 4,
+                _ => 8,
+            };
+            Some(DefLayout {
+                layout: TypeLayout::primitive(size),
+                fields: vec![],
+            })
+        }
+        IdlTypeDefTy::Type { alias } => Some(DefLayout {
+            layout: type_layout(defs, struct_opts, alias)?,
+            fields: vec![],
+        }),
+        _ => None,
+    }
+}
+
+/// Generates compile time checks that a zero copy struct has the layout described by the IDL.
+///
+/// Each field offset is checked, so that a mismatch names the offending field.
+/// Types without a fixed layout are not checked, and types which contain 128-bit
+/// integers are only checked when building for the chain.
+pub fn generate_layout_asserts(
+    defs: &[IdlTypeDef],
+    def: &IdlTypeDef,
+    struct_opts: &BTreeMap<String, StructOpts>,
+) -> TokenStream {
+    if !matches!(def.ty, IdlTypeDefTy::Struct { .. }) {
+        return quote! {};
+    }
+    let Some(DefLayout { layout, fields }) = def_layout(defs, struct_opts, def) else {
+        return quote! {};
+    };
+    let struct_name = format_ident!("{}", def.name);
+    // `target_os = "solana"` is unknown to the host's cfg checks.
+    let (allow, cfg) = if layout.uses_int128 {
+        (
+            quote! { #[allow(unexpected_cfgs)] },
+            quote! { #[cfg(target_os = "solana")] },
+        )
+    } else {
+        (quote! {}, quote! {})
+    };
+    let field_asserts = fields.iter().map(|field| {
+        let FieldOffset {
+            name,
+            member,
+            offset,
+        } = field;
+        let message = format!(
+            "field `{}` of `{}` is not at offset {} as expected from the IDL",
+            name, def.name, offset
+        );
+        quote! {
+            #cfg
+            assert!(std::mem::offset_of!(#struct_name, #member) == #offset, #message);
+        }
+    });
+    let size = layout.size;
+    let message = format!(
+        "`{}` is not {} bytes as expected from the IDL",
+        def.name, size
+    );
+    quote! {
+        #allow
+        const _: () = {
+            #(#field_asserts)*
+            #cfg
+            assert!(std::mem::size_of::<#struct_name>() == #size, #message);
+        };
+    }
+}
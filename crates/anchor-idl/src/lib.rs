@@ -3,26 +3,62 @@
 pub use anchor_lang_idl_spec::*;
 
 mod account;
+mod builder;
+mod config;
+mod constant;
+mod error;
 mod event;
 mod fields;
+mod generator_error;
 mod instruction;
+mod int256;
+mod layout;
+mod pda;
 mod program;
 mod state;
+mod target;
 mod typedef;
 
 pub use account::*;
+pub use builder::*;
+pub use config::*;
+pub use constant::*;
+pub use error::*;
 pub use event::*;
+pub use generator_error::*;
 pub use instruction::*;
+pub use int256::*;
+pub use layout::*;
+pub use pda::*;
 pub use program::*;
 pub use state::*;
+pub use target::*;
 pub use typedef::*;
 
 /// Version of anchor-idl.
 pub const GEN_VERSION: Option<&str> = option_env!("CARGO_PKG_VERSION");
 
+/// Generates `#[doc]` attributes from a list of IDL doc lines.
+pub fn generate_docs(docs: &[String]) -> proc_macro2::TokenStream {
+    let lines = docs.iter().map(|line| format!(" {}", line));
+    quote::quote! {
+        #(#[doc = #lines])*
+    }
+}
+
+/// Generates a discriminator as an array expression of its bytes.
+pub fn generate_discriminator(discriminator: &[u8]) -> proc_macro2::TokenStream {
+    let bytes = discriminator
+        .iter()
+        .map(|b| proc_macro2::Literal::u8_unsuffixed(*b));
+    quote::quote! {
+        [#(#bytes),*]
+    }
+}
+
 /// Converts an [IdlType] to a [String] of the Rust representation.
-pub fn ty_to_rust_type(ty: &IdlType) -> String {
-    match ty {
+pub fn ty_to_rust_type(ty: &IdlType) -> GeneratorResult<String> {
+    Ok(match ty {
         IdlType::Bool => "bool".to_string(),
         IdlType::U8 => "u8".to_string(),
         IdlType::I8 => "i8".to_string(),
@@ -39,20 +75,38 @@ pub fn ty_to_rust_type(ty: &IdlType) -> String {
         IdlType::Bytes => "Vec<u8>".to_string(),
         IdlType::String => "String".to_string(),
         IdlType::Pubkey => "Pubkey".to_string(),
-        IdlType::Option(inner) => format!("Option<{}>", ty_to_rust_type(inner)),
-        IdlType::Vec(inner) => format!("Vec<{}>", ty_to_rust_type(inner)),
+        IdlType::Option(inner) => format!("Option<{}>", ty_to_rust_type(inner)?),
+        IdlType::Vec(inner) => format!("Vec<{}>", ty_to_rust_type(inner)?),
         IdlType::Array(ty, size) => match size {
             IdlArrayLen::Generic(name) => {
-                format!("[{}; {}]", ty_to_rust_type(ty), *name)
+                format!("[{}; {}]", ty_to_rust_type(ty)?, *name)
             }
             IdlArrayLen::Value(size) => {
-                format!("[{}; {}]", ty_to_rust_type(ty), *size)
+                format!("[{}; {}]", ty_to_rust_type(ty)?, *size)
             }
         },
-        IdlType::Defined { name, generics: _ } => name.to_string(),
-        IdlType::U256 => todo!(),
-        IdlType::I256 => todo!(),
-        IdlType::Generic(_) => todo!(),
-        _ => todo!(),
-    }
+        IdlType::Defined { name, generics } => {
+            if generics.is_empty() {
+                name.to_string()
+            } else {
+                let args = generics
+                    .iter()
+                    .map(|arg| match arg {
+                        IdlGenericArg::Type { ty } => ty_to_rust_type(ty),
+                        IdlGenericArg::Const { value } => Ok(value.to_string()),
+                    })
+                    .collect::<GeneratorResult<Vec<_>>>()?;
+                format!("{}<{}>", name, args.join(", "))
+            }
+        }
+        IdlType::U256 => "U256".to_string(),
+        IdlType::I256 => "I256".to_string(),
+        IdlType::Generic(name) => name.to_string(),
+        _ => return Err(unsupported_type(ty)),
+    })
+}
+
+/// Creates the error for an [IdlType] which is newer than the generator.
+pub(crate) fn unsupported_type(ty: &IdlType) -> GeneratorError {
+    GeneratorError::unsupported(format!("{:?}", ty), "type is not supported")
 }
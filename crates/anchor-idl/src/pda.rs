@@ -0,0 +1,243 @@
+use std::collections::BTreeMap;
+
+use anchor_lang_idl_spec::{
+    IdlArrayLen, IdlDefinedFields, IdlInstruction, IdlInstructionAccount,
+    IdlInstructionAccountItem, IdlPda, IdlSeed, IdlType, IdlTypeDef, IdlTypeDefTy,
+};
+use heck::ToSnakeCase;
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote};
+
+/// A PDA declared on an instruction account.
+struct PdaAccount<'a> {
+    ix: &'a IdlInstruction,
+    account: &'a IdlInstructionAccount,
+    pda: &'a IdlPda,
+}
+
+/// A seed which is passed to the derivation helper as a parameter.
+struct SeedParam {
+    name: Ident,
+    ty: TokenStream,
+}
+
+fn collect_pda_accounts<'a>(
+    ix: &'a IdlInstruction,
+    accounts: &'a [IdlInstructionAccountItem],
+    out: &mut Vec<PdaAccount<'a>>,
+) {
+    for item in accounts {
+        match item {
+            IdlInstructionAccountItem::Single(account) => {
+                if let Some(pda) = &account.pda {
+                    out.push(PdaAccount { ix, account, pda });
+                }
+            }
+            IdlInstructionAccountItem::Composite(inner) => {
+                collect_pda_accounts(ix, &inner.accounts, out)
+            }
+        }
+    }
+}
+
+/// Resolves the type of a field path such as `governor.proposal_count`,
+/// starting from the type of its first segment.
+fn resolve_path_type(defs: &[IdlTypeDef], root: IdlType, rest: &[&str]) -> Option<IdlType> {
+    rest.iter().try_fold(root, |ty, segment| {
+        let IdlType::Defined { name, .. } = ty else {
+            return None;
+        };
+        let def = defs.iter().find(|def| def.name == name)?;
+        match &def.ty {
+            IdlTypeDefTy::Struct {
+                fields: Some(IdlDefinedFields::Named(fields)),
+            } => fields
+                .iter()
+                .find(|field| field.name.to_snake_case() == segment.to_snake_case())
+                .map(|field| field.ty.clone()),
+            _ => None,
+        }
+    })
+}
+
+/// Resolves the type of an `arg` or `account` seed.
+fn resolve_seed_type(
+    defs: &[IdlTypeDef],
+    ix: &IdlInstruction,
+    seed: &IdlSeed,
+) -> Result<(String, IdlType), String> {
+    match seed {
+        IdlSeed::Const(_) => Err("constant seeds have no parameter".to_string()),
+        IdlSeed::Arg(arg) => {
+            let segments = arg.path.split('.').collect::<Vec<_>>();
+            let root = ix
+                .args
+                .iter()
+                .find(|a| a.name.to_snake_case() == segments[0].to_snake_case())
+                .ok_or_else(|| format!("unknown argument `{}`", arg.path))?;
+            let ty = resolve_path_type(defs, root.ty.clone(), &segments[1..])
+                .ok_or_else(|| format!("cannot resolve the type of argument `{}`", arg.path))?;
+            Ok((arg.path.clone(), ty))
+        }
+        IdlSeed::Account(account) => {
+            let segments = account.path.split('.').collect::<Vec<_>>();
+            if segments.len() == 1 {
+                return Ok((account.path.clone(), IdlType::Pubkey));
+            }
+            let ty = account
+                .account
+                .as_ref()
+                .and_then(|name| {
+                    let root = IdlType::Defined {
+                        name: name.clone(),
+                        generics: vec![],
+                    };
+                    resolve_path_type(defs, root, &segments[1..])
+                })
+                .ok_or_else(|| format!("cannot resolve the type of account `{}`", account.path))?;
+            Ok((account.path.clone(), ty))
+        }
+    }
+}
+
+/// Gets the parameter type of a seed and the expression of its bytes.
+fn seed_param_type(name: &Ident, ty: &IdlType) -> Option<(TokenStream, TokenStream)> {
+    Some(match ty {
+        IdlType::Pubkey => (quote! { Pubkey }, quote! { #name.as_ref() }),
+        IdlType::U8
+        | IdlType::I8
+        | IdlType::U16
+        | IdlType::I16
+        | IdlType::U32
+        | IdlType::I32
+        | IdlType::U64
+        | IdlType::I64
+        | IdlType::U128
+        | IdlType::I128 => {
+            let ty: TokenStream = crate::ty_to_rust_type(ty).ok()?.parse().unwrap();
+            (quote! { #ty }, quote! { &#name.to_le_bytes() })
+        }
+        IdlType::Bool => (quote! { bool }, quote! { &[#name as u8] }),
+        IdlType::String => (quote! { &str }, quote! { #name.as_bytes() }),
+        IdlType::Bytes => (quote! { &[u8] }, quote! { #name }),
+        IdlType::Array(inner, IdlArrayLen::Value(len)) if matches!(**inner, IdlType::U8) => {
+            (quote! { [u8; #len] }, quote! { &#name })
+        }
+        _ => return None,
+    })
+}
+
+/// Generates a PDA derivation helper, or the reason why it cannot be generated.
+fn generate_pda_fn(
+    defs: &[IdlTypeDef],
+    fn_name: &Ident,
+    pda_account: &PdaAccount,
+) -> Result<TokenStream, String> {
+    let PdaAccount { pda, .. } = pda_account;
+
+    let mut params: Vec<SeedParam> = vec![];
+    // Returns the parameter of a seed, its type and the expression of its bytes.
+    let mut param_for_seed = |seed: &IdlSeed| -> Result<(Ident, IdlType, TokenStream), String> {
+        let (path, ty) = resolve_seed_type(defs, pda_account.ix, seed)?;
+        let name = format_ident!("{}", path.replace('.', "_").to_snake_case());
+        let (param_ty, bytes) = seed_param_type(&name, &ty)
+            .ok_or_else(|| format!("unsupported seed type {:?} for `{}`", ty, path))?;
+        if !params.iter().any(|p| p.name == name) {
+            params.push(SeedParam {
+                name: name.clone(),
+                ty: param_ty,
+            });
+        }
+        Ok((name, ty, bytes))
+    };
+
+    let seeds = pda
+        .seeds
+        .iter()
+        .map(|seed| match seed {
+            IdlSeed::Const(value) => {
+                let bytes = &value.value;
+                Ok(quote! { &[#(#bytes),*] })
+            }
+            _ => param_for_seed(seed).map(|(_, _, bytes)| bytes),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let program_id = match &pda.program {
+        None => quote! { &ID },
+        Some(IdlSeed::Const(value)) => {
+            let bytes = &value.value;
+            if bytes.len() != 32 {
+                return Err(format!(
+                    "expected a 32 byte program ID, got {} bytes",
+                    bytes.len()
+                ));
+            }
+            quote! { &Pubkey::new_from_array([#(#bytes),*]) }
+        }
+        Some(seed) => {
+            let (name, ty, _) = param_for_seed(seed)?;
+            if ty != IdlType::Pubkey {
+                return Err(format!("program `{}` is not a Pubkey", name));
+            }
+            quote! { &#name }
+        }
+    };
+
+    let doc = format!(
+        " Finds the address of the `{}` account.",
+        pda_account.account.name
+    );
+    let param_names = params.iter().map(|p| &p.name);
+    let param_tys = params.iter().map(|p| &p.ty);
+    Ok(quote! {
+        #[doc = #doc]
+        pub fn #fn_name(#(#param_names: #param_tys),*) -> (Pubkey, u8) {
+            Pubkey::find_program_address(&[#(#seeds),*], #program_id)
+        }
+    })
+}
+
+/// Generates PDA derivation helpers for all instruction accounts which declare seeds.
+///
+/// Helpers are named after the account. If accounts of the same name have different
+/// seeds in different instructions, their helpers are prefixed with the instruction name.
+/// Accounts whose seeds cannot be resolved, such as fields of accounts which are not
+/// defined in the IDL, have no helper.
+pub fn generate_pdas(defs: &[IdlTypeDef], ixs: &[IdlInstruction]) -> TokenStream {
+    let mut pda_accounts = vec![];
+    for ix in ixs {
+        collect_pda_accounts(ix, &ix.accounts, &mut pda_accounts);
+    }
+
+    let mut by_name: BTreeMap<String, Vec<PdaAccount>> = BTreeMap::new();
+    for pda_account in pda_accounts {
+        let same_name = by_name
+            .entry(pda_account.account.name.to_snake_case())
+            .or_default();
+        if !same_name.iter().any(|other| other.pda == pda_account.pda) {
+            same_name.push(pda_account);
+        }
+    }
+
+    let mut fns = vec![];
+    for (name, same_name) in &by_name {
+        for pda_account in same_name {
+            let fn_name = if same_name.len() == 1 {
+                format_ident!("find_{}_address", name)
+            } else {
+                format_ident!(
+                    "find_{}_{}_address",
+                    pda_account.ix.name.to_snake_case(),
+                    name
+                )
+            };
+            if let Ok(pda_fn) = generate_pda_fn(defs, &fn_name, pda_account) {
+                fns.push(pda_fn);
+            }
+        }
+    }
+    quote! {
+        #(#fns)*
+    }
+}
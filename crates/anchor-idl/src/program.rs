@@ -1,145 +1,505 @@
 use std::{
     collections::{BTreeMap, HashSet},
     env, fs,
-    path::PathBuf,
+    path::{Path, PathBuf},
+    str::FromStr,
 };
 
-use darling::{util::PathList, FromMeta};
-use proc_macro2::{Ident, TokenStream};
+use darling::{
+    util::{PathList, SpannedValue},
+    FromMeta,
+};
+use heck::ToPascalCase;
+use proc_macro2::{Ident, Literal, TokenStream};
 use quote::{format_ident, quote};
 
 use crate::{
-    generate_accounts, generate_events, generate_ix_handlers, generate_ix_structs,
-    generate_typedefs, GEN_VERSION,
+    generate_accounts, generate_constants, generate_errors, generate_events, generate_int256_types,
+    generate_ix_builder_fns, generate_ix_client_structs, generate_ix_cpi_client_accounts,
+    generate_ix_cpi_fns, generate_ix_cpi_return, generate_ix_data_structs, generate_ix_enum,
+    generate_ix_invoke_fns, generate_ix_pinocchio_fns, generate_ix_structs, generate_pdas,
+    generate_typedefs, is_fieldless_enum, parse_fixed_address, rename_types, resolve_layouts,
+    GeneratorConfig, GeneratorError, GeneratorResult, Target, GEN_VERSION,
 };
 
 #[derive(Default, FromMeta)]
 pub struct GeneratorOptions {
     /// Path to the IDL.
-    pub idl_path: String,
+    pub idl_path: SpannedValue<String>,
     /// List of types to skip from generation. These should be provided by the caller instead.
     pub skip: Option<PathList>,
     /// List of zero copy structs.
     pub zero_copy: Option<PathList>,
     /// List of `repr(packed)` structs.
     pub packed: Option<PathList>,
+    /// Path to a TOML config file with additional options, relative to the crate's Cargo.toml.
+    pub config_path: Option<String>,
+    /// Framework which the generated code is written for: `anchor`, `no-anchor` or `pinocchio`.
+    pub target: Option<String>,
 }
 
-fn path_list_to_string(list: Option<&PathList>) -> HashSet<String> {
+fn path_list_to_string(
+    option: &'static str,
+    list: Option<&PathList>,
+) -> GeneratorResult<Vec<String>> {
     list.map(|el| {
         el.iter()
-            .map(|el| el.get_ident().unwrap().to_string())
+            .map(|el| {
+                el.get_ident()
+                    .map(|ident| ident.to_string())
+                    .ok_or_else(|| GeneratorError::InvalidTypePath {
+                        option,
+                        path: quote!(#el).to_string(),
+                    })
+            })
             .collect()
     })
-    .unwrap_or_default()
+    .unwrap_or_else(|| Ok(vec![]))
 }
 
-impl GeneratorOptions {
-    pub fn to_generator(&self) -> Generator {
-        let cargo_manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
-        let path = PathBuf::from(cargo_manifest_dir).join(&self.idl_path);
-        let idl_contents = fs::read_to_string(&path).unwrap();
-        let idl: anchor_lang_idl_spec::Idl = serde_json::from_str(&idl_contents).unwrap();
-
-        let skip = path_list_to_string(self.skip.as_ref());
-        let zero_copy = path_list_to_string(self.zero_copy.as_ref());
-        let packed = path_list_to_string(self.packed.as_ref());
+/// Reads and parses the IDL at the given path.
+pub fn load_idl(path: &Path) -> GeneratorResult<anchor_lang_idl_spec::Idl> {
+    let idl_contents = fs::read_to_string(path).map_err(|error| GeneratorError::ReadIdl {
+        path: path.to_path_buf(),
+        error,
+    })?;
+    serde_json::from_str(&idl_contents).map_err(|error| GeneratorError::ParseIdl {
+        path: path.to_path_buf(),
+        line: error.line(),
+        column: error.column(),
+        message: error.to_string(),
+    })
+}
 
-        let all_type_names = idl
-            .accounts
-            .iter()
-            .map(|a| a.name.clone())
-            .chain(idl.types.iter().map(|t| t.name.clone()))
-            .collect::<HashSet<_>>();
+impl GeneratorOptions {
+    pub fn to_generator(&self) -> GeneratorResult<Generator> {
+        let cargo_manifest_dir =
+            env::var("CARGO_MANIFEST_DIR").map_err(|_| GeneratorError::MissingManifestDir)?;
+        let manifest_dir = PathBuf::from(cargo_manifest_dir);
+        let idl = load_idl(&manifest_dir.join(self.idl_path.as_str()))?;
 
-        let mut struct_opts: BTreeMap<String, StructOpts> = BTreeMap::new();
-        all_type_names.iter().for_each(|name| {
-            struct_opts.insert(
-                name.to_string(),
-                StructOpts {
-                    skip: skip.contains(name),
-                    zero_copy: zero_copy.contains(name),
-                    packed: packed.contains(name),
-                },
-            );
+        // Options of the macro take precedence over the config file.
+        let mut config = match &self.config_path {
+            Some(config_path) => GeneratorConfig::load(&manifest_dir.join(config_path))?,
+            None => GeneratorConfig::default(),
+        };
+        config.merge(GeneratorConfig {
+            skip: path_list_to_string("skip", self.skip.as_ref())?,
+            zero_copy: path_list_to_string("zero_copy", self.zero_copy.as_ref())?,
+            packed: path_list_to_string("packed", self.packed.as_ref())?,
+            target: self.target.as_deref().map(Target::from_str).transpose()?,
+            ..Default::default()
         });
 
-        Generator { idl, struct_opts }
+        Generator::new(idl, &config)
     }
 }
 
-#[derive(Clone, Copy, Debug, Default)]
+#[derive(Clone, Debug, Default)]
 pub struct StructOpts {
     pub skip: bool,
     pub packed: bool,
     pub zero_copy: bool,
+    /// Minimum alignment of a zero copy struct, from `repr(align(N))`.
+    pub align: Option<usize>,
+    /// Additional traits to derive.
+    pub derives: Vec<String>,
+    /// Integer representation of an enum without fields.
+    pub repr: Option<String>,
+    /// Discriminant of the first variant of an enum without fields.
+    pub first_discriminant: i64,
+}
+
+impl StructOpts {
+    /// Generates the `#[derive]` attribute of the additional traits.
+    pub fn generate_derives(&self) -> TokenStream {
+        if self.derives.is_empty() {
+            return quote! {};
+        }
+        let derives = self
+            .derives
+            .iter()
+            .map(|derive| syn::parse_str::<syn::Path>(derive).unwrap());
+        quote! {
+            #[derive(#(#derives),*)]
+        }
+    }
+
+    /// Generates the `#[repr]` attribute of a zero copy struct.
+    pub fn generate_repr(&self) -> TokenStream {
+        if self.packed {
+            return quote! {
+                #[repr(C, packed)]
+            };
+        }
+        match self.align {
+            Some(align) => {
+                let align = Literal::usize_unsuffixed(align);
+                quote! {
+                    #[repr(C, align(#align))]
+                }
+            }
+            None => quote! {
+                #[repr(C)]
+            },
+        }
+    }
 }
 
 pub struct Generator {
     pub idl: anchor_lang_idl_spec::Idl,
     pub struct_opts: BTreeMap<String, StructOpts>,
+    pub target: Target,
 }
 
 impl Generator {
-    pub fn generate_cpi_interface(&self) -> TokenStream {
-        let idl = &self.idl;
-        let program_name: Ident = format_ident!("{}", idl.metadata.name);
+    /// Creates a [Generator] for an IDL with the given options.
+    pub fn new(
+        mut idl: anchor_lang_idl_spec::Idl,
+        config: &GeneratorConfig,
+    ) -> GeneratorResult<Generator> {
+        config.validate(&idl)?;
 
-        let accounts = generate_accounts(&idl.types, &idl.accounts, &self.struct_opts);
-        let events = generate_events(&idl.events, &idl.types, &self.struct_opts);
-        let typedefs = generate_typedefs(&idl.types, &self.struct_opts);
-        let ix_handlers = generate_ix_handlers(&idl.instructions);
-        let ix_structs = generate_ix_structs(&idl.instructions);
+        let layouts = resolve_layouts(&idl, config);
+        let all_type_names = idl
+            .accounts
+            .iter()
+            .map(|a| a.name.clone())
+            .chain(idl.types.iter().map(|t| t.name.clone()))
+            .collect::<HashSet<_>>();
 
-        let docs = format!(
-        " Anchor CPI crate generated from {} v{} using [anchor-gen](https://crates.io/crates/anchor-gen) v{}.",
-        &idl.metadata.name,
-        &idl.metadata.version,
-        &GEN_VERSION.unwrap_or("unknown")
-    );
+        let renames = config.renames();
+        let mut struct_opts: BTreeMap<String, StructOpts> = BTreeMap::new();
+        for name in &all_type_names {
+            let type_config = config.type_config(name);
+            if type_config.repr.is_some() || type_config.first_discriminant.is_some() {
+                let def = idl.types.iter().find(|def| &def.name == name);
+                if !def.map(is_fieldless_enum).unwrap_or(false) {
+                    return Err(GeneratorError::unsupported(
+                        name,
+                        "`repr` and `first_discriminant` require an enum without fields",
+                    ));
+                }
+            }
+            let layout = layouts.get(name).copied().unwrap_or_default();
+            if layout.packed && layout.align.is_some() {
+                return Err(GeneratorError::unsupported(
+                    name,
+                    "`packed` structs may not have an `align` representation",
+                ));
+            }
+            struct_opts.insert(
+                renames.get(name).unwrap_or(name).to_string(),
+                StructOpts {
+                    skip: type_config.skip,
+                    zero_copy: layout.zero_copy,
+                    packed: layout.packed,
+                    align: layout.align,
+                    derives: type_config.derives,
+                    repr: type_config.repr,
+                    first_discriminant: type_config.first_discriminant.unwrap_or_default(),
+                },
+            );
+        }
+        rename_types(&mut idl, &renames);
 
-        let address = idl.address.clone();
+        Ok(Generator {
+            idl,
+            struct_opts,
+            target: config.target.unwrap_or_default(),
+        })
+    }
 
-        quote! {
-            use anchor_lang::prelude::*;
+    pub fn generate_cpi_interface(&self) -> GeneratorResult<TokenStream> {
+        let (prelude, modules) = match self.target {
+            Target::Anchor => self.generate_anchor_modules()?,
+            Target::NoAnchor => self.generate_no_anchor_modules()?,
+            Target::Pinocchio => self.generate_pinocchio_modules()?,
+        };
+        let common_modules = self.generate_common_modules()?;
+        Ok(quote! {
+            #prelude
 
-            declare_id!(#address);
+            #common_modules
+
+            #modules
+        })
+    }
+
+    /// Generates the formatted code of the CPI crate.
+    pub fn generate_formatted(&self) -> GeneratorResult<String> {
+        let tokens = self.generate_cpi_interface()?;
+        let file = syn::parse2::<syn::File>(tokens).map_err(GeneratorError::FormatOutput)?;
+        Ok(prettyplease::unparse(&file))
+    }
+
+    /// Generates the types, constants, state, events and errors, which are in the same
+    /// modules for every target.
+    fn generate_common_modules(&self) -> GeneratorResult<TokenStream> {
+        let idl = &self.idl;
+        let target = self.target;
+
+        let accounts = generate_accounts(&idl.types, &idl.accounts, &self.struct_opts, target)?;
+        let events = generate_events(&idl.events, &idl.types, &self.struct_opts, target)?;
+        let errors = generate_errors(&idl.errors, target);
+        let constants = generate_constants(&idl.constants, target)?;
+        let typedefs = generate_typedefs(&idl.types, &self.struct_opts, target)?;
+        let int256_types = generate_int256_types(idl, target);
+        let use_super = use_super();
+
+        Ok(quote! {
+            #int256_types
 
             pub mod typedefs {
                 //! User-defined types.
-                use super::*;
+                #use_super
                 #typedefs
             }
 
+            pub mod constants {
+                //! Constants defined by the program.
+                #use_super
+                #constants
+            }
+
             pub mod state {
                 //! Structs of accounts which hold state.
-                use super::*;
+                #use_super
                 #accounts
             }
 
             pub mod events {
                 //! Structs of events generated by program.
-                use super::*;
+                #use_super
                 #events
             }
 
+            pub mod errors {
+                //! Errors returned by the program.
+                #use_super
+                #errors
+            }
+
+            pub use state::*;
+            pub use typedefs::*;
+        })
+    }
+
+    /// Generates the imports and program ID, and the instruction modules of an Anchor crate.
+    fn generate_anchor_modules(&self) -> GeneratorResult<(TokenStream, TokenStream)> {
+        let idl = &self.idl;
+        let target = Target::Anchor;
+        let program_name: Ident = format_ident!("{}", idl.metadata.name.to_pascal_case());
+
+        let ix_structs = generate_ix_structs(&idl.instructions, target);
+        let ix_data_structs = generate_ix_data_structs(&idl.instructions, target)?;
+        let ix_enum = generate_ix_enum(&idl.instructions);
+        let ix_cpi_fns = generate_ix_cpi_fns(&idl.instructions)?;
+        let ix_cpi_return = generate_ix_cpi_return();
+        let client_structs =
+            generate_ix_client_structs(&idl.types, &idl.accounts, &idl.instructions, target);
+        let cpi_client_accounts = generate_ix_cpi_client_accounts(&idl.instructions);
+        let pdas = generate_pdas(&idl.types, &idl.instructions);
+
+        let docs = format!(
+        " Anchor CPI crate generated from {} v{} using [anchor-gen](https://crates.io/crates/anchor-gen) v{}.",
+        &idl.metadata.name,
+        &idl.metadata.version,
+        &GEN_VERSION.unwrap_or("unknown")
+    );
+
+        let address = idl.address.clone();
+        let use_super = use_super();
+
+        let prelude = quote! {
+            use anchor_lang::prelude::*;
+
+            declare_id!(#address);
+        };
+        let modules = quote! {
             pub mod ix_accounts {
                 //! Accounts used in instructions.
-                use super::*;
+                #use_super
                 #ix_structs
             }
 
-            use ix_accounts::*;
-            pub use state::*;
-            pub use typedefs::*;
+            pub mod pda {
+                //! Derivation of program derived addresses used by instructions.
+                #use_super
+                #pdas
+            }
 
-            #[program]
-            pub mod #program_name {
+            pub mod program {
                 #![doc = #docs]
 
                 use super::*;
-                #ix_handlers
+
+                /// Type representing the program.
+                #[derive(Clone)]
+                pub struct #program_name;
+
+                impl anchor_lang::Id for #program_name {
+                    fn id() -> Pubkey {
+                        ID
+                    }
+                }
             }
-        }
+
+            pub mod instruction {
+                //! Instruction data for each instruction of the program.
+                #use_super
+                #ix_data_structs
+
+                #ix_enum
+            }
+
+            pub mod accounts {
+                //! Accounts of each instruction, where each account is a [Pubkey].
+                //! This is useful for building instructions from a client.
+                #use_super
+                #client_structs
+            }
+
+            #[cfg(feature = "cpi")]
+            pub mod cpi {
+                //! Helpers for invoking the program via CPI.
+                use super::*;
+
+                pub mod accounts {
+                    //! Accounts of each instruction, where each account is an [AccountInfo].
+                    #cpi_client_accounts
+                }
+
+                #ix_cpi_return
+
+                #ix_cpi_fns
+            }
+        };
+        Ok((prelude, modules))
+    }
+
+    /// Generates the imports and program ID, and the instruction modules of a crate
+    /// which uses `borsh`, `bytemuck` and `solana_program` instead of Anchor.
+    fn generate_no_anchor_modules(&self) -> GeneratorResult<(TokenStream, TokenStream)> {
+        let idl = &self.idl;
+        let target = Target::NoAnchor;
+
+        let ix_data_structs = generate_ix_data_structs(&idl.instructions, target)?;
+        let ix_builder_fns = generate_ix_builder_fns(&idl.instructions)?;
+        let ix_invoke_fns = generate_ix_invoke_fns(&idl.instructions)?;
+        let client_structs =
+            generate_ix_client_structs(&idl.types, &idl.accounts, &idl.instructions, target);
+        let pdas = generate_pdas(&idl.types, &idl.instructions);
+
+        let address = idl.address.clone();
+        let use_super = use_super();
+
+        let prelude = quote! {
+            #[allow(unused_imports)]
+            use solana_program::{
+                account_info::AccountInfo, entrypoint::ProgramResult,
+                program_error::ProgramError, pubkey::Pubkey,
+            };
+
+            solana_program::declare_id!(#address);
+        };
+        let modules = quote! {
+            pub mod pda {
+                //! Derivation of program derived addresses used by instructions.
+                #use_super
+                #pdas
+            }
+
+            pub mod instruction {
+                //! Instruction data and builders for each instruction of the program.
+                #use_super
+                #ix_data_structs
+
+                #ix_builder_fns
+            }
+
+            pub mod accounts {
+                //! Accounts of each instruction, where each account is a [Pubkey].
+                //! This is useful for building instructions from a client.
+                #use_super
+                #client_structs
+            }
+
+            #[cfg(feature = "cpi")]
+            pub mod cpi {
+                //! Helpers for invoking the program via CPI.
+                #use_super
+                #ix_invoke_fns
+            }
+        };
+        Ok((prelude, modules))
+    }
+
+    /// Generates the imports and program ID, and the zero-allocation CPI helpers of a
+    /// crate which uses Pinocchio.
+    fn generate_pinocchio_modules(&self) -> GeneratorResult<(TokenStream, TokenStream)> {
+        let idl = &self.idl;
+        let target = Target::Pinocchio;
+
+        let ix_structs = generate_ix_structs(&idl.instructions, target);
+        let ix_data_structs = generate_ix_data_structs(&idl.instructions, target)?;
+        let ix_pinocchio_fns = generate_ix_pinocchio_fns(&idl.types, &idl.instructions)?;
+
+        // Pinocchio has no `declare_id!`, so the ID is decoded here.
+        let address = parse_fixed_address(&idl.address).ok_or_else(|| {
+            GeneratorError::unsupported(
+                &idl.metadata.name,
+                format!("invalid program address `{}`", idl.address),
+            )
+        })?;
+        let id = target.pubkey(&address);
+        let use_super = use_super();
+
+        let prelude = quote! {
+            #[allow(unused_imports)]
+            use pinocchio::{
+                account_info::AccountInfo,
+                instruction::{AccountMeta, Instruction, Signer},
+                program_error::ProgramError,
+                pubkey::Pubkey,
+                ProgramResult,
+            };
+
+            /// The program ID.
+            pub const ID: Pubkey = #id;
+        };
+        let modules = quote! {
+            pub mod instruction {
+                //! Instruction data for each instruction of the program.
+                #use_super
+                #ix_data_structs
+            }
+
+            #[cfg(feature = "cpi")]
+            pub mod cpi {
+                //! Helpers for invoking the program via CPI, which do not allocate.
+                //! Instructions whose arguments have no maximum length, such as strings
+                //! and vectors, take the buffer to serialize their data into.
+                #use_super
+
+                pub mod accounts {
+                    //! Accounts of each instruction, where each account is an [AccountInfo].
+                    #use_super
+                    #ix_structs
+                }
+
+                #ix_pinocchio_fns
+            }
+        };
+        Ok((prelude, modules))
+    }
+}
+
+/// Imports the crate root into a generated module.
+fn use_super() -> TokenStream {
+    // Modules may not use anything from the crate root, e.g. if they are empty.
+    quote! {
+        #[allow(unused_imports)]
+        use super::*;
     }
 }
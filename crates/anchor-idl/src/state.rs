@@ -1,22 +1,38 @@
 use std::collections::BTreeMap;
 
-use anchor_lang_idl_spec::{IdlAccount, IdlField, IdlTypeDef};
-use proc_macro2::TokenStream;
+use anchor_lang_idl_spec::{IdlAccount, IdlDefinedFields, IdlTypeDef};
+use proc_macro2::{Ident, TokenStream};
 use quote::{format_ident, quote};
 
 use crate::{
-    fields::{generate_struct_fields_from_slice, get_idl_defined_fields_as_slice},
-    get_field_list_properties, StructOpts,
+    fields::generate_struct_body, generate_layout_asserts, get_defined_fields_list_properties,
+    pod_enum_fields, GeneratorError, GeneratorResult, StructOpts, Target,
 };
 
 /// Generates an account state struct.
+#[allow(clippy::too_many_arguments)]
 pub fn generate_account(
     defs: &[IdlTypeDef],
     account_name: &str,
-    fields: &[IdlField],
-    opts: StructOpts,
-) -> TokenStream {
-    let props = get_field_list_properties(defs, fields);
+    discriminator: &[u8],
+    docs: &[String],
+    fields: &Option<IdlDefinedFields>,
+    opts: &StructOpts,
+    struct_opts: &BTreeMap<String, StructOpts>,
+    target: Target,
+) -> GeneratorResult<TokenStream> {
+    if opts.zero_copy && matches!(fields, Some(IdlDefinedFields::Tuple(_))) {
+        return Err(GeneratorError::unsupported(
+            account_name,
+            "zero copy accounts must have named fields",
+        ));
+    }
+    let props = get_defined_fields_list_properties(defs, fields)?;
+    let fields = &if opts.zero_copy {
+        pod_enum_fields(defs, fields, struct_opts)
+    } else {
+        fields.clone()
+    };
 
     let derive_copy = if props.can_copy && !opts.zero_copy {
         quote! {
@@ -32,38 +48,94 @@ pub fn generate_account(
     } else {
         quote! {}
     };
-    let derive_account = if opts.zero_copy {
-        let repr = if opts.packed {
-            quote! {
-                #[repr(packed)]
-            }
-        } else {
+    let discriminator = crate::generate_discriminator(discriminator);
+    let struct_name = format_ident!("{}", account_name);
+    if target != Target::Anchor {
+        return generate_no_anchor_account(
+            &struct_name,
+            &discriminator,
+            docs,
+            fields,
+            opts,
             quote! {
-                #[repr(C)]
-            }
-        };
+                #derive_copy
+                #derive_default
+            },
+        );
+    }
+    let derive_account = if opts.zero_copy {
+        let repr = opts.generate_repr();
         quote! {
-            #[account(zero_copy(unsafe))]
+            #[account(zero_copy(unsafe), discriminator = #discriminator)]
             #repr
         }
     } else {
         quote! {
-            #[account]
+            #[account(discriminator = #discriminator)]
         }
     };
 
     let doc = format!(" Account: {}", account_name);
-    let struct_name = format_ident!("{}", account_name);
-    let fields_rendered = generate_struct_fields_from_slice(fields);
-    quote! {
+    let extra_derives = opts.generate_derives();
+    let docs = crate::generate_docs(docs);
+    let body = generate_struct_body(fields)?;
+    Ok(quote! {
         #derive_account
+        #docs
         #[doc = #doc]
         #derive_copy
         #derive_default
-        pub struct #struct_name {
-            #fields_rendered
+        #extra_derives
+        pub struct #struct_name #body
+    })
+}
+
+/// Generates an account state struct without Anchor, with a constant of its discriminator.
+fn generate_no_anchor_account(
+    struct_name: &Ident,
+    discriminator: &TokenStream,
+    docs: &[String],
+    fields: &Option<IdlDefinedFields>,
+    opts: &StructOpts,
+    derives: TokenStream,
+) -> GeneratorResult<TokenStream> {
+    let (derive_serializers, pod_impls) = if opts.zero_copy {
+        let repr = opts.generate_repr();
+        (
+            quote! {
+                #[derive(Clone, Copy)]
+                #repr
+            },
+            quote! {
+                unsafe impl ::bytemuck::Pod for #struct_name {}
+                unsafe impl ::bytemuck::Zeroable for #struct_name {}
+            },
+        )
+    } else {
+        (
+            quote! {
+                #[derive(::borsh::BorshSerialize, ::borsh::BorshDeserialize, Clone)]
+            },
+            quote! {},
+        )
+    };
+    let extra_derives = opts.generate_derives();
+    let docs = crate::generate_docs(docs);
+    let body = generate_struct_body(fields)?;
+    Ok(quote! {
+        #docs
+        #derive_serializers
+        #derives
+        #extra_derives
+        pub struct #struct_name #body
+
+        #pod_impls
+
+        impl #struct_name {
+            /// Discriminator at the start of the account's data.
+            pub const DISCRIMINATOR: &'static [u8] = &#discriminator;
         }
-    }
+    })
 }
 
 /// Generates account state structs.
@@ -71,33 +143,114 @@ pub fn generate_accounts(
     typedefs: &[IdlTypeDef],
     account_defs: &[IdlAccount],
     struct_opts: &BTreeMap<String, StructOpts>,
-) -> TokenStream {
+    target: Target,
+) -> GeneratorResult<TokenStream> {
     let defined = account_defs
         .iter()
         .map(|account| {
-            typedefs
+            let def = typedefs
                 .iter()
                 .find(|type_def| type_def.name == account.name)
-                .unwrap()
+                .ok_or_else(|| {
+                    GeneratorError::unsupported(&account.name, "account has no type definition")
+                })?;
+            match &def.ty {
+                anchor_lang_idl_spec::IdlTypeDefTy::Struct { fields } => {
+                    let opts = struct_opts.get(&def.name).cloned().unwrap_or_default();
+                    let generated = generate_account(
+                        typedefs,
+                        &def.name,
+                        &account.discriminator,
+                        &def.docs,
+                        fields,
+                        &opts,
+                        struct_opts,
+                        target,
+                    )?;
+                    let layout_asserts = if opts.zero_copy {
+                        generate_layout_asserts(typedefs, def, struct_opts)
+                    } else {
+                        quote! {}
+                    };
+                    Ok(quote! {
+                        #generated
+                        #layout_asserts
+                    })
+                }
+                anchor_lang_idl_spec::IdlTypeDefTy::Enum { .. } => Err(
+                    GeneratorError::unsupported(&def.name, "unexpected enum account"),
+                ),
+                anchor_lang_idl_spec::IdlTypeDefTy::Type { alias: _ } => Err(
+                    GeneratorError::unsupported(&def.name, "unexpected type alias account"),
+                ),
+            }
         })
-        .map(|def| match &def.ty {
-            anchor_lang_idl_spec::IdlTypeDefTy::Struct { fields } => {
-                let opts = struct_opts.get(&def.name).copied().unwrap_or_default();
-                generate_account(
-                    typedefs,
-                    &def.name,
-                    get_idl_defined_fields_as_slice(fields),
-                    opts,
-                )
+        .collect::<GeneratorResult<Vec<_>>>()?;
+    let any_account = match target {
+        Target::Anchor => generate_any_account(account_defs, struct_opts),
+        Target::NoAnchor | Target::Pinocchio => quote! {},
+    };
+    Ok(quote! {
+        #(#defined)*
+
+        #any_account
+    })
+}
+
+/// Generates an `AnyAccount` enum over all accounts, which decodes accounts by their discriminator.
+pub fn generate_any_account(
+    account_defs: &[IdlAccount],
+    struct_opts: &BTreeMap<String, StructOpts>,
+) -> TokenStream {
+    if account_defs.is_empty() {
+        return quote! {};
+    }
+    let names = account_defs
+        .iter()
+        .map(|account| format_ident!("{}", account.name))
+        .collect::<Vec<_>>();
+    let decoders = account_defs.iter().zip(&names).map(|(account, name)| {
+        let zero_copy = struct_opts
+            .get(&account.name)
+            .is_some_and(|opts| opts.zero_copy);
+        let decode = if zero_copy {
+            // Account data is not necessarily aligned, and may be longer than the struct.
+            quote! {
+                let start = #name::DISCRIMINATOR.len();
+                let data = data
+                    .get(start..start + std::mem::size_of::<#name>())
+                    .ok_or(anchor_lang::error::ErrorCode::AccountDidNotDeserialize)?;
+                let account = anchor_lang::__private::bytemuck::try_pod_read_unaligned(data)
+                    .map_err(|_| anchor_lang::error::ErrorCode::AccountDidNotDeserialize)?;
             }
-            anchor_lang_idl_spec::IdlTypeDefTy::Enum { .. } => {
-                panic!("unexpected enum account");
+        } else {
+            quote! {
+                let account = #name::try_deserialize_unchecked(&mut &*data)?;
             }
-            anchor_lang_idl_spec::IdlTypeDefTy::Type { alias: _ } => {
-                panic!("unexpected type account")
+        };
+        quote! {
+            if data.starts_with(#name::DISCRIMINATOR) {
+                #decode
+                return Ok(Some(Self::#name(account)));
             }
-        });
+        }
+    });
     quote! {
-        #(#defined)*
+        /// An account owned by the program.
+        #[derive(Clone)]
+        #[allow(clippy::large_enum_variant)]
+        pub enum AnyAccount {
+            #(#names(#names)),*
+        }
+
+        impl AnyAccount {
+            /// Decodes an account from its data, which starts with the discriminator of the account.
+            ///
+            /// Returns [None] if the data is not an account of this program.
+            pub fn try_from_bytes(data: &[u8]) -> Result<Option<Self>> {
+                #(#decoders)*
+                Ok(None)
+            }
+        }
     }
 }
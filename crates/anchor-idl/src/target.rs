@@ -0,0 +1,64 @@
+use std::str::FromStr;
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use serde::Deserialize;
+
+use crate::GeneratorError;
+
+/// Framework which the generated code is written for.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Target {
+    /// Anchor accounts, events and CPI helpers using `anchor_lang`.
+    #[default]
+    Anchor,
+    /// Plain `borsh` and `bytemuck` types, with instruction builders and CPI helpers
+    /// using `solana_program`.
+    NoAnchor,
+    /// Plain `borsh` and `bytemuck` types, with zero-allocation CPI helpers using `pinocchio`.
+    Pinocchio,
+}
+
+impl FromStr for Target {
+    type Err = GeneratorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "anchor" => Ok(Target::Anchor),
+            "no-anchor" => Ok(Target::NoAnchor),
+            "pinocchio" => Ok(Target::Pinocchio),
+            _ => Err(GeneratorError::UnknownTarget(s.to_string())),
+        }
+    }
+}
+
+impl Target {
+    /// Gets the derives of the Borsh serialization traits.
+    pub fn borsh_derives(self) -> TokenStream {
+        match self {
+            Target::Anchor => quote! { AnchorSerialize, AnchorDeserialize },
+            Target::NoAnchor | Target::Pinocchio => {
+                quote! { ::borsh::BorshSerialize, ::borsh::BorshDeserialize }
+            }
+        }
+    }
+
+    /// Gets the path of the `bytemuck` crate.
+    pub fn bytemuck(self) -> TokenStream {
+        match self {
+            Target::Anchor => quote! { anchor_lang::__private::bytemuck },
+            Target::NoAnchor | Target::Pinocchio => quote! { ::bytemuck },
+        }
+    }
+
+    /// Gets an expression of a `Pubkey` from its bytes.
+    ///
+    /// Pinocchio's `Pubkey` is a plain byte array.
+    pub fn pubkey(self, bytes: &[u8]) -> TokenStream {
+        match self {
+            Target::Anchor | Target::NoAnchor => quote! { Pubkey::new_from_array([#(#bytes),*]) },
+            Target::Pinocchio => quote! { [#(#bytes),*] },
+        }
+    }
+}
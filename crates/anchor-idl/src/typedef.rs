@@ -1,15 +1,17 @@
 use std::collections::BTreeMap;
 
 use anchor_lang_idl_spec::{
-    IdlArrayLen, IdlDefinedFields, IdlEnumVariant, IdlField, IdlType, IdlTypeDef,
+    IdlArrayLen, IdlDefinedFields, IdlEnumVariant, IdlField, IdlGenericArg, IdlType, IdlTypeDef,
+    IdlTypeDefGeneric, IdlTypeDefTy,
 };
 use heck::ToSnakeCase;
-use proc_macro2::{Ident, TokenStream};
+use proc_macro2::{Ident, Literal, TokenStream};
 use quote::{format_ident, quote};
+use syn::Generics;
 
 use crate::{
-    fields::{generate_struct_fields, get_idl_defined_fields_as_slice},
-    StructOpts,
+    fields::generate_struct_body, generate_layout_asserts, GeneratorError, GeneratorResult,
+    StructOpts, Target,
 };
 
 #[derive(Copy, Clone, Debug, Default)]
@@ -21,7 +23,7 @@ pub struct FieldListProperties {
 pub fn get_defined_fields_list_properties(
     defs: &[IdlTypeDef],
     fields: &Option<IdlDefinedFields>,
-) -> FieldListProperties {
+) -> GeneratorResult<FieldListProperties> {
     let types = fields
         .iter()
         .flat_map(|f| match f {
@@ -32,27 +34,33 @@ pub fn get_defined_fields_list_properties(
     get_type_list_properties(defs, &types)
 }
 
-pub fn get_field_list_properties(defs: &[IdlTypeDef], fields: &[IdlField]) -> FieldListProperties {
+pub fn get_field_list_properties(
+    defs: &[IdlTypeDef],
+    fields: &[IdlField],
+) -> GeneratorResult<FieldListProperties> {
     get_type_list_properties(
         defs,
         &fields.iter().map(|f| f.ty.clone()).collect::<Vec<_>>(),
     )
 }
 
-pub fn get_type_list_properties(defs: &[IdlTypeDef], fields: &[IdlType]) -> FieldListProperties {
-    fields.iter().fold(
+pub fn get_type_list_properties(
+    defs: &[IdlTypeDef],
+    fields: &[IdlType],
+) -> GeneratorResult<FieldListProperties> {
+    fields.iter().try_fold(
         FieldListProperties {
             can_copy: true,
             can_derive_default: true,
         },
         |acc, el| {
-            let inner_props = get_type_properties(defs, el);
+            let inner_props = get_type_properties(defs, el)?;
             let can_copy = acc.can_copy && inner_props.can_copy;
             let can_derive_default = acc.can_derive_default && inner_props.can_derive_default;
-            FieldListProperties {
+            Ok(FieldListProperties {
                 can_copy,
                 can_derive_default,
-            }
+            })
         },
     )
 }
@@ -60,28 +68,31 @@ pub fn get_type_list_properties(defs: &[IdlTypeDef], fields: &[IdlType]) -> Fiel
 pub fn get_variant_list_properties(
     defs: &[IdlTypeDef],
     variants: &[IdlEnumVariant],
-) -> FieldListProperties {
-    variants.iter().fold(
+) -> GeneratorResult<FieldListProperties> {
+    variants.iter().try_fold(
         FieldListProperties {
             can_copy: true,
             can_derive_default: true,
         },
         |acc, el| {
             let props = match &el.fields {
-                Some(IdlDefinedFields::Named(fields)) => get_field_list_properties(defs, fields),
-                Some(IdlDefinedFields::Tuple(fields)) => get_type_list_properties(defs, fields),
+                Some(IdlDefinedFields::Named(fields)) => get_field_list_properties(defs, fields)?,
+                Some(IdlDefinedFields::Tuple(fields)) => get_type_list_properties(defs, fields)?,
                 None => acc,
             };
-            FieldListProperties {
+            Ok(FieldListProperties {
                 can_copy: acc.can_copy && props.can_copy,
                 can_derive_default: acc.can_derive_default && props.can_derive_default,
-            }
+            })
         },
     )
 }
 
-pub fn get_type_properties(defs: &[IdlTypeDef], ty: &IdlType) -> FieldListProperties {
-    match ty {
+pub fn get_type_properties(
+    defs: &[IdlTypeDef],
+    ty: &IdlType,
+) -> GeneratorResult<FieldListProperties> {
+    Ok(match ty {
         IdlType::Bool
         | IdlType::U8
         | IdlType::I8
@@ -95,6 +106,8 @@ pub fn get_type_properties(defs: &[IdlTypeDef], ty: &IdlType) -> FieldListProper
         | IdlType::F64
         | IdlType::U128
         | IdlType::I128
+        | IdlType::U256
+        | IdlType::I256
         | IdlType::Pubkey => FieldListProperties {
             can_copy: true,
             can_derive_default: true,
@@ -107,21 +120,39 @@ pub fn get_type_properties(defs: &[IdlTypeDef], ty: &IdlType) -> FieldListProper
             can_copy: false,
             can_derive_default: true,
         },
-        IdlType::Defined { name, .. } => {
-            let def = defs.iter().find(|def| def.name == *name).unwrap();
-            match &def.ty {
+        IdlType::Defined { name, generics } => {
+            let def = defs
+                .iter()
+                .find(|def| def.name == *name)
+                .ok_or_else(|| GeneratorError::unsupported(name, "type is not defined"))?;
+            // Derives on generic types are bounded by their type arguments.
+            let args = generics
+                .iter()
+                .filter_map(|arg| match arg {
+                    IdlGenericArg::Type { ty } => Some(ty.clone()),
+                    IdlGenericArg::Const { .. } => None,
+                })
+                .collect::<Vec<_>>();
+            let args_props = get_type_list_properties(defs, &args)?;
+            let props = match &def.ty {
                 anchor_lang_idl_spec::IdlTypeDefTy::Struct { fields } => {
-                    get_field_list_properties(defs, get_idl_defined_fields_as_slice(fields))
+                    get_defined_fields_list_properties(defs, fields)?
                 }
                 anchor_lang_idl_spec::IdlTypeDefTy::Enum { variants } => {
-                    get_variant_list_properties(defs, variants)
+                    get_variant_list_properties(defs, variants)?
+                }
+                anchor_lang_idl_spec::IdlTypeDefTy::Type { alias } => {
+                    get_type_properties(defs, alias)?
                 }
-                anchor_lang_idl_spec::IdlTypeDefTy::Type { alias: _ } => todo!(),
+            };
+            FieldListProperties {
+                can_copy: props.can_copy && args_props.can_copy,
+                can_derive_default: props.can_derive_default && args_props.can_derive_default,
             }
         }
-        IdlType::Option(inner) => get_type_properties(defs, inner),
+        IdlType::Option(inner) => get_type_properties(defs, inner)?,
         IdlType::Array(inner, len) => {
-            let inner = get_type_properties(defs, inner);
+            let inner = get_type_properties(defs, inner)?;
             let can_derive_array_len = match len {
                 IdlArrayLen::Value(len) => *len <= 32,
                 IdlArrayLen::Generic(_) => false,
@@ -131,51 +162,94 @@ pub fn get_type_properties(defs: &[IdlTypeDef], ty: &IdlType) -> FieldListProper
                 can_derive_default: can_derive_array_len && inner.can_derive_default,
             }
         }
-        IdlType::U256 => todo!(),
-        IdlType::I256 => todo!(),
-        IdlType::Generic(_) => todo!(),
-        _ => todo!(),
-    }
+        // Derives on generic types are bounded by their type parameters,
+        // so the type parameter itself never prevents a derive.
+        IdlType::Generic(_) => FieldListProperties {
+            can_copy: true,
+            can_derive_default: true,
+        },
+        _ => return Err(crate::unsupported_type(ty)),
+    })
 }
 
 /// Generates enum fields from a list of [IdlField]s.
-pub fn generate_enum_fields(fields: &[IdlField]) -> TokenStream {
-    let fields_rendered = fields.iter().map(|arg| {
-        let name = format_ident!("{}", arg.name.to_snake_case());
-        let type_name = crate::ty_to_rust_type(&arg.ty);
-        let stream: proc_macro2::TokenStream = type_name.parse().unwrap();
-        quote! {
-            #name: #stream
-        }
-    });
-    quote! {
+pub fn generate_enum_fields(fields: &[IdlField]) -> GeneratorResult<TokenStream> {
+    let fields_rendered = fields
+        .iter()
+        .map(|arg| {
+            let name = format_ident!("{}", arg.name.to_snake_case());
+            let type_name = crate::ty_to_rust_type(&arg.ty)?;
+            let stream: proc_macro2::TokenStream = type_name.parse().unwrap();
+            let docs = crate::generate_docs(&arg.docs);
+            Ok(quote! {
+                #docs
+                #name: #stream
+            })
+        })
+        .collect::<GeneratorResult<Vec<_>>>()?;
+    Ok(quote! {
         #(#fields_rendered),*
-    }
+    })
 }
 
 /// Generates enum tuple types from a list of [IdlType]s.
-pub fn generate_enum_tuple_types(fields: &[IdlType]) -> TokenStream {
-    let fields_rendered = fields.iter().map(|arg| {
-        let type_name = crate::ty_to_rust_type(arg);
-        let stream: proc_macro2::TokenStream = type_name.parse().unwrap();
-        quote! {
-            #stream
-        }
-    });
-    quote! {
+pub fn generate_enum_tuple_types(fields: &[IdlType]) -> GeneratorResult<TokenStream> {
+    let fields_rendered = fields
+        .iter()
+        .map(|arg| {
+            let type_name = crate::ty_to_rust_type(arg)?;
+            let stream: proc_macro2::TokenStream = type_name.parse().unwrap();
+            Ok(quote! {
+                #stream
+            })
+        })
+        .collect::<GeneratorResult<Vec<_>>>()?;
+    Ok(quote! {
         #(#fields_rendered),*
+    })
+}
+
+/// Generates the generic parameters of a type definition from a list of [IdlTypeDefGeneric]s.
+pub fn generate_generics(generics: &[IdlTypeDefGeneric]) -> Generics {
+    let params = generics
+        .iter()
+        .map(|generic| match generic {
+            IdlTypeDefGeneric::Type { name } => name.to_string(),
+            IdlTypeDefGeneric::Const { name, ty } => format!("const {}: {}", name, ty),
+        })
+        .collect::<Vec<_>>();
+    if params.is_empty() {
+        Generics::default()
+    } else {
+        syn::parse_str(&format!("<{}>", params.join(", "))).unwrap()
     }
 }
 
 /// Generates a struct.
+#[allow(clippy::too_many_arguments)]
 pub fn generate_struct(
     defs: &[IdlTypeDef],
     struct_name: &Ident,
+    generics: &Generics,
+    docs: &[String],
     fields: &Option<IdlDefinedFields>,
-    opts: StructOpts,
-) -> TokenStream {
-    let fields_rendered = generate_struct_fields(fields);
-    let props = get_field_list_properties(defs, get_idl_defined_fields_as_slice(fields));
+    opts: &StructOpts,
+    struct_opts: &BTreeMap<String, StructOpts>,
+    target: Target,
+) -> GeneratorResult<TokenStream> {
+    // Whether a generic struct has padding depends on its type arguments.
+    if opts.zero_copy && !generics.params.is_empty() {
+        return Err(GeneratorError::unsupported(
+            struct_name.to_string(),
+            "zero copy structs may not be generic",
+        ));
+    }
+    let body = if opts.zero_copy {
+        generate_struct_body(&pod_enum_fields(defs, fields, struct_opts))?
+    } else {
+        generate_struct_body(fields)?
+    };
+    let props = get_defined_fields_list_properties(defs, fields)?;
 
     let derive_default = if props.can_derive_default {
         quote! {
@@ -184,19 +258,31 @@ pub fn generate_struct(
     } else {
         quote! {}
     };
+    let is_tuple = matches!(fields, Some(IdlDefinedFields::Tuple(_)));
     let derive_serializers = if opts.zero_copy {
-        let repr = if opts.packed {
+        let repr = opts.generate_repr();
+        // `#[zero_copy]` derives field accessors, which require named fields.
+        let zero_copy = if is_tuple || target != Target::Anchor {
             quote! {
-                #[repr(packed)]
+                #[derive(Copy, Clone)]
             }
         } else {
             quote! {
-                #[repr(C)]
+                #[zero_copy(unsafe)]
+            }
+        };
+        // The Borsh derives take references to the fields, which may be unaligned in
+        // packed structs.
+        let borsh_derives = if opts.packed {
+            quote! {}
+        } else {
+            quote! {
+                #[derive(::borsh::BorshSerialize, ::borsh::BorshDeserialize)]
             }
         };
         quote! {
-            #[derive(::borsh::BorshSerialize, ::borsh::BorshDeserialize)]
-            #[zero_copy(unsafe)]
+            #borsh_derives
+            #zero_copy
             #repr
         }
     } else {
@@ -207,53 +293,76 @@ pub fn generate_struct(
         } else {
             quote! {}
         };
+        let borsh_derives = target.borsh_derives();
         quote! {
-            #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+            #[derive(#borsh_derives, Clone)]
             #derive_copy
         }
     };
 
-    quote! {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let pod_impls = if opts.zero_copy && (is_tuple || target != Target::Anchor) {
+        let bytemuck = target.bytemuck();
+        quote! {
+            unsafe impl #impl_generics #bytemuck::Pod for #struct_name #ty_generics #where_clause {}
+            unsafe impl #impl_generics #bytemuck::Zeroable for #struct_name #ty_generics #where_clause {}
+        }
+    } else {
+        quote! {}
+    };
+
+    let extra_derives = opts.generate_derives();
+    let docs = crate::generate_docs(docs);
+    Ok(quote! {
+        #docs
         #derive_serializers
         #[derive(Debug)]
         #derive_default
-        pub struct #struct_name {
-            #fields_rendered
-        }
-    }
+        #extra_derives
+        pub struct #struct_name #generics #body
+
+        #pod_impls
+    })
 }
 
 /// Generates an enum.
 pub fn generate_enum(
     defs: &[IdlTypeDef],
     enum_name: &Ident,
+    generics: &Generics,
+    docs: &[String],
     variants: &[IdlEnumVariant],
-) -> TokenStream {
-    let variant_idents = variants.iter().map(|v| {
-        let name = format_ident!("{}", v.name);
-        match &v.fields {
-            Some(IdlDefinedFields::Named(idl_fields)) => {
-                let fields = generate_enum_fields(idl_fields);
-                quote! {
-                  #name {
-                    #fields
-                  }
+    opts: &StructOpts,
+    target: Target,
+) -> GeneratorResult<TokenStream> {
+    let variant_idents = variants
+        .iter()
+        .map(|v| {
+            let name = format_ident!("{}", v.name);
+            Ok(match &v.fields {
+                Some(IdlDefinedFields::Named(idl_fields)) => {
+                    let fields = generate_enum_fields(idl_fields)?;
+                    quote! {
+                      #name {
+                        #fields
+                      }
+                    }
                 }
-            }
-            Some(IdlDefinedFields::Tuple(idl_fields)) => {
-                let types = generate_enum_tuple_types(idl_fields);
-                quote! {
-                  #name(#types)
+                Some(IdlDefinedFields::Tuple(idl_fields)) => {
+                    let types = generate_enum_tuple_types(idl_fields)?;
+                    quote! {
+                      #name(#types)
+                    }
                 }
-            }
-            _ => {
-                quote! {
-                  #name
+                _ => {
+                    quote! {
+                      #name
+                    }
                 }
-            }
-        }
-    });
-    let props = get_variant_list_properties(defs, variants);
+            })
+        })
+        .collect::<GeneratorResult<Vec<_>>>()?;
+    let props = get_variant_list_properties(defs, variants)?;
 
     let derive_copy = if props.can_copy {
         quote! {
@@ -276,54 +385,281 @@ pub fn generate_enum(
         }) if !types.is_empty() => {
             quote! {}
         }
-        _ => {
-            let default_variant = format_ident!("{}", variants.first().unwrap().name);
+        Some(variant) => {
+            let default_variant = format_ident!("{}", variant.name);
+            let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+            // Code written by the CLI is linted with the rest of the user's crate, where
+            // clippy would suggest deriving this impl.
             quote! {
-              impl Default for #enum_name {
+              #[allow(clippy::derivable_impls)]
+              impl #impl_generics Default for #enum_name #ty_generics #where_clause {
                   fn default() -> Self {
                       Self::#default_variant
                   }
               }
             }
         }
+        None => {
+            return Err(GeneratorError::unsupported(
+                enum_name.to_string(),
+                "enums must have at least one variant",
+            ))
+        }
     };
 
+    let docs = crate::generate_docs(docs);
+    let extra_derives = opts.generate_derives();
+    let is_fieldless = variants.iter().all(|v| !has_fields(v)) && generics.params.is_empty();
+    if is_fieldless && (opts.zero_copy || opts.repr.is_some()) {
+        return Ok(generate_repr_enum(
+            enum_name,
+            docs,
+            variants,
+            opts,
+            target,
+            default_impl,
+        ));
+    }
+    let borsh_derives = target.borsh_derives();
     let result = quote! {
-        #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+        #docs
+        #[derive(#borsh_derives, Clone, Debug)]
         #derive_copy
-        pub enum #enum_name {
+        #extra_derives
+        pub enum #enum_name #generics {
             #(#variant_idents),*
         }
 
         #default_impl
     };
 
-    result
+    Ok(result)
+}
+
+fn has_fields(variant: &IdlEnumVariant) -> bool {
+    match &variant.fields {
+        Some(IdlDefinedFields::Named(fields)) => !fields.is_empty(),
+        Some(IdlDefinedFields::Tuple(types)) => !types.is_empty(),
+        None => false,
+    }
+}
+
+/// Returns true if the type is an enum whose variants have no fields.
+pub fn is_fieldless_enum(def: &IdlTypeDef) -> bool {
+    match &def.ty {
+        IdlTypeDefTy::Enum { variants } => {
+            def.generics.is_empty() && variants.iter().all(|v| !has_fields(v))
+        }
+        _ => false,
+    }
+}
+
+/// Replaces the zero copy enums without fields in `fields` by their `Pod{Name}` wrappers.
+///
+/// Zero copy structs may be read from arbitrary bytes, which must not contain invalid
+/// enum discriminants.
+pub fn pod_enum_fields(
+    defs: &[IdlTypeDef],
+    fields: &Option<IdlDefinedFields>,
+    struct_opts: &BTreeMap<String, StructOpts>,
+) -> Option<IdlDefinedFields> {
+    fields.as_ref().map(|fields| match fields {
+        IdlDefinedFields::Named(fields) => IdlDefinedFields::Named(
+            fields
+                .iter()
+                .map(|field| IdlField {
+                    ty: pod_enum_type(defs, &field.ty, struct_opts),
+                    ..field.clone()
+                })
+                .collect(),
+        ),
+        IdlDefinedFields::Tuple(fields) => IdlDefinedFields::Tuple(
+            fields
+                .iter()
+                .map(|ty| pod_enum_type(defs, ty, struct_opts))
+                .collect(),
+        ),
+    })
+}
+
+fn pod_enum_type(
+    defs: &[IdlTypeDef],
+    ty: &IdlType,
+    struct_opts: &BTreeMap<String, StructOpts>,
+) -> IdlType {
+    match ty {
+        IdlType::Defined { name, generics } if generics.is_empty() => {
+            let is_pod_enum = defs
+                .iter()
+                .any(|def| &def.name == name && is_fieldless_enum(def))
+                && struct_opts
+                    .get(name)
+                    .is_some_and(|opts| opts.zero_copy && !opts.skip);
+            if is_pod_enum {
+                IdlType::Defined {
+                    name: format!("Pod{}", name),
+                    generics: vec![],
+                }
+            } else {
+                ty.clone()
+            }
+        }
+        IdlType::Array(inner, len) => IdlType::Array(
+            Box::new(pod_enum_type(defs, inner, struct_opts)),
+            len.clone(),
+        ),
+        _ => ty.clone(),
+    }
+}
+
+/// Generates an enum without fields with an integer `repr` and explicit discriminants.
+///
+/// Zero copy enums also get a `Pod{Name}` wrapper of their integer value, since enums
+/// may not be read from arbitrary bytes.
+fn generate_repr_enum(
+    enum_name: &Ident,
+    docs: TokenStream,
+    variants: &[IdlEnumVariant],
+    opts: &StructOpts,
+    target: Target,
+    default_impl: TokenStream,
+) -> TokenStream {
+    let repr = format_ident!("{}", opts.repr.as_deref().unwrap_or("u8"));
+    let names = variants
+        .iter()
+        .map(|v| format_ident!("{}", v.name))
+        .collect::<Vec<_>>();
+    let discriminants = (0..variants.len())
+        .map(|i| Literal::i64_unsuffixed(opts.first_discriminant + i as i64))
+        .collect::<Vec<_>>();
+    let extra_derives = opts.generate_derives();
+    let bytemuck = target.bytemuck();
+
+    let pod = if opts.zero_copy {
+        let pod_name = format_ident!("Pod{}", enum_name);
+        let doc = format!(" Zero copy representation of [{}].", enum_name);
+        quote! {
+            #[doc = #doc]
+            #[derive(::borsh::BorshSerialize, ::borsh::BorshDeserialize)]
+            #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+            #[repr(transparent)]
+            pub struct #pod_name(pub #repr);
+
+            unsafe impl #bytemuck::Pod for #pod_name {}
+            unsafe impl #bytemuck::Zeroable for #pod_name {}
+
+            impl From<#enum_name> for #pod_name {
+                fn from(value: #enum_name) -> Self {
+                    Self(value as #repr)
+                }
+            }
+
+            impl TryFrom<#pod_name> for #enum_name {
+                type Error = ProgramError;
+
+                fn try_from(value: #pod_name) -> std::result::Result<Self, Self::Error> {
+                    match value.0 {
+                        #(#discriminants => Ok(Self::#names),)*
+                        _ => Err(ProgramError::InvalidAccountData),
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let borsh_derives = target.borsh_derives();
+    // Borsh 1.0 requires enums with explicit discriminants to choose how they are
+    // serialized. Anchor always serializes the variant index.
+    let use_discriminant = match target {
+        Target::Anchor => quote! {},
+        Target::NoAnchor | Target::Pinocchio => quote! { #[borsh(use_discriminant = false)] },
+    };
+    quote! {
+        #docs
+        #[derive(#borsh_derives, Clone, Copy, Debug)]
+        #use_discriminant
+        #extra_derives
+        #[repr(#repr)]
+        pub enum #enum_name {
+            #(#names = #discriminants),*
+        }
+
+        #default_impl
+
+        #pod
+    }
+}
+
+/// Generates a type alias.
+pub fn generate_alias(
+    alias_name: &Ident,
+    generics: &Generics,
+    docs: &[String],
+    alias: &IdlType,
+) -> GeneratorResult<TokenStream> {
+    let type_name = crate::ty_to_rust_type(alias)?;
+    let stream: proc_macro2::TokenStream = type_name.parse().unwrap();
+    let docs = crate::generate_docs(docs);
+    Ok(quote! {
+        #docs
+        pub type #alias_name #generics = #stream;
+    })
 }
 
-/// Generates structs and enums.
+/// Generates structs, enums and type aliases.
 pub fn generate_typedefs(
     typedefs: &[IdlTypeDef],
     struct_opts: &BTreeMap<String, StructOpts>,
-) -> TokenStream {
+    target: Target,
+) -> GeneratorResult<TokenStream> {
     let defined = typedefs.iter().map(|def| {
-        let opts = struct_opts.get(&def.name).copied().unwrap_or_default();
+        let opts = struct_opts.get(&def.name).cloned().unwrap_or_default();
         if opts.skip {
-            quote! {}
+            Ok(quote! {})
         } else {
             let struct_name = format_ident!("{}", def.name);
+            let generics = generate_generics(&def.generics);
             match &def.ty {
                 anchor_lang_idl_spec::IdlTypeDefTy::Struct { fields } => {
-                    generate_struct(typedefs, &struct_name, fields, opts)
+                    let generated = generate_struct(
+                        typedefs,
+                        &struct_name,
+                        &generics,
+                        &def.docs,
+                        fields,
+                        &opts,
+                        struct_opts,
+                        target,
+                    )?;
+                    let layout_asserts = if opts.zero_copy {
+                        generate_layout_asserts(typedefs, def, struct_opts)
+                    } else {
+                        quote! {}
+                    };
+                    Ok(quote! {
+                        #generated
+                        #layout_asserts
+                    })
                 }
-                anchor_lang_idl_spec::IdlTypeDefTy::Enum { variants } => {
-                    generate_enum(typedefs, &struct_name, variants)
+                anchor_lang_idl_spec::IdlTypeDefTy::Enum { variants } => generate_enum(
+                    typedefs,
+                    &struct_name,
+                    &generics,
+                    &def.docs,
+                    variants,
+                    &opts,
+                    target,
+                ),
+                anchor_lang_idl_spec::IdlTypeDefTy::Type { alias } => {
+                    generate_alias(&struct_name, &generics, &def.docs, alias)
                 }
-                anchor_lang_idl_spec::IdlTypeDefTy::Type { alias: _ } => todo!(),
             }
         }
     });
-    quote! {
+    let defined = defined.collect::<GeneratorResult<Vec<_>>>()?;
+    Ok(quote! {
         #(#defined)*
-    }
+    })
 }
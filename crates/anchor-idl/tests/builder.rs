@@ -0,0 +1,234 @@
+use std::{env, fs, path::Path, process};
+
+use anchor_idl::{
+    generate_constant, load_idl, Builder, Generator, GeneratorConfig, GeneratorError, IdlConst,
+    IdlDefinedFields, IdlField, IdlInstructionAccountItem, IdlSeed, IdlSeedAccount, IdlType,
+    IdlTypeDef, IdlTypeDefTy, Target,
+};
+
+/// Example IDLs, relative to this crate, with their config files.
+const EXAMPLES: &[(&str, Option<&str>)] = &[
+    ("../../examples/farms/idl.json", None),
+    (
+        "../../examples/fixture/idl.json",
+        Some("../../examples/fixture/anchor-gen.toml"),
+    ),
+    ("../../examples/govern-cpi/idl.json", None),
+    (
+        "../../examples/kamino-lend/idl.json",
+        Some("../../examples/kamino-lend/anchor-gen.toml"),
+    ),
+    ("../../examples/marinade-cpi/idl.json", None),
+];
+
+fn builder(idl_path: &str, config_path: Option<&str>) -> Builder {
+    let builder = Builder::new(idl_path);
+    match config_path {
+        Some(config_path) => builder.config_path(config_path),
+        None => builder,
+    }
+}
+
+#[test]
+fn generates_examples() {
+    for (idl_path, config_path) in EXAMPLES {
+        let code = builder(idl_path, *config_path)
+            .generate()
+            .unwrap_or_else(|err| panic!("{}: {}", idl_path, err));
+        let file = syn::parse_file(&code).unwrap_or_else(|err| panic!("{}: {}", idl_path, err));
+
+        let idl = load_idl(Path::new(idl_path)).unwrap();
+        let events = module_items(&file.items, "events");
+        for event in &idl.events {
+            assert!(
+                has_type(events, &event.name),
+                "{}: missing event {}",
+                idl_path,
+                event.name
+            );
+        }
+        assert_eq!(
+            has_type(events, "Event"),
+            !idl.events.is_empty(),
+            "{}",
+            idl_path
+        );
+    }
+}
+
+/// Gets the items of a top-level module of the generated code.
+fn module_items<'a>(items: &'a [syn::Item], name: &str) -> &'a [syn::Item] {
+    items
+        .iter()
+        .find_map(|item| match item {
+            syn::Item::Mod(module) if module.ident == name => {
+                module.content.as_ref().map(|(_, items)| items.as_slice())
+            }
+            _ => None,
+        })
+        .unwrap_or_else(|| panic!("missing module {}", name))
+}
+
+fn has_type(items: &[syn::Item], name: &str) -> bool {
+    items.iter().any(|item| match item {
+        syn::Item::Struct(item) => item.ident == name,
+        syn::Item::Enum(item) => item.ident == name,
+        _ => false,
+    })
+}
+
+#[test]
+fn builder_options_override_config_file() {
+    let config_path = env::temp_dir().join(format!("anchor-idl-{}-target.toml", process::id()));
+    fs::write(&config_path, "target = \"pinocchio\"\n").unwrap();
+    let code = Builder::new("../../examples/govern-cpi/idl.json")
+        .config_path(&config_path)
+        .target(Target::NoAnchor)
+        .generate()
+        .unwrap();
+    fs::remove_file(&config_path).unwrap();
+    assert!(code.contains("solana_program::declare_id!"));
+    assert!(!code.contains("pinocchio"));
+}
+
+#[test]
+fn unknown_types_are_errors() {
+    let error = Builder::new("../../examples/govern-cpi/idl.json")
+        .rename("Missing", "Other")
+        .generate()
+        .unwrap_err();
+    assert!(matches!(error, GeneratorError::UnknownType(name) if name == "Missing"));
+}
+
+#[test]
+fn undefined_types_are_errors() {
+    let mut idl = load_idl(Path::new("../../examples/govern-cpi/idl.json")).unwrap();
+    let fields = idl
+        .types
+        .iter_mut()
+        .find_map(|def| match &mut def.ty {
+            IdlTypeDefTy::Struct {
+                fields: Some(IdlDefinedFields::Named(fields)),
+            } => Some(fields),
+            _ => None,
+        })
+        .unwrap();
+    fields.push(IdlField {
+        name: "missing".to_string(),
+        docs: vec![],
+        ty: IdlType::Defined {
+            name: "Missing".to_string(),
+            generics: vec![],
+        },
+    });
+    let error = Generator::new(idl, &GeneratorConfig::default())
+        .unwrap()
+        .generate_cpi_interface()
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        GeneratorError::UnsupportedItem { item, .. } if item == "Missing"
+    ));
+}
+
+#[test]
+fn layout_asserts_name_fields() {
+    let code = builder(
+        "../../examples/fixture/idl.json",
+        Some("../../examples/fixture/anchor-gen.toml"),
+    )
+    .generate()
+    .unwrap();
+    assert!(code.contains("std::mem::offset_of!(Reserve, total) == 40"));
+    assert!(
+        code.contains("field `total` of `Reserve` is not at offset 40 as expected from the IDL")
+    );
+    assert!(code.contains("`Reserve` is not 88 bytes as expected from the IDL"));
+}
+
+#[test]
+fn generic_zero_copy_structs_are_errors() {
+    let error = builder(
+        "../../examples/fixture/idl.json",
+        Some("../../examples/fixture/anchor-gen.toml"),
+    )
+    .zero_copy("Pair")
+    .generate()
+    .unwrap_err();
+    assert!(matches!(
+        error,
+        GeneratorError::UnsupportedItem { item, .. } if item == "Pair"
+    ));
+}
+
+#[test]
+fn enums_without_variants_are_errors() {
+    let mut idl = load_idl(Path::new("../../examples/govern-cpi/idl.json")).unwrap();
+    idl.types.push(IdlTypeDef {
+        name: "Empty".to_string(),
+        docs: vec![],
+        serialization: Default::default(),
+        repr: None,
+        generics: vec![],
+        ty: IdlTypeDefTy::Enum { variants: vec![] },
+    });
+    let error = Generator::new(idl, &GeneratorConfig::default())
+        .unwrap()
+        .generate_cpi_interface()
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        GeneratorError::UnsupportedItem { item, .. } if item == "Empty"
+    ));
+}
+
+#[test]
+fn signed_constants_are_range_checked() {
+    let constant = |ty: IdlType, value: &str| IdlConst {
+        name: "VALUE".to_string(),
+        docs: vec![],
+        ty,
+        value: value.to_string(),
+    };
+    let code = generate_constant(
+        &constant(IdlType::I128, "-170141183460469231731687303715884105728"),
+        Target::Anchor,
+    )
+    .unwrap();
+    assert!(code
+        .to_string()
+        .contains("170141183460469231731687303715884105728i128"));
+    assert!(matches!(
+        generate_constant(&constant(IdlType::I8, "-129"), Target::Anchor),
+        Err(GeneratorError::UnsupportedItem { item, .. }) if item == "VALUE"
+    ));
+    assert!(generate_constant(&constant(IdlType::I8, "-128"), Target::Anchor).is_ok());
+}
+
+#[test]
+fn unresolvable_pdas_are_skipped() {
+    let mut idl = load_idl(Path::new("../../examples/govern-cpi/idl.json")).unwrap();
+    let create_governor = idl
+        .instructions
+        .iter_mut()
+        .find(|ix| ix.name == "create_governor")
+        .unwrap();
+    let Some(IdlInstructionAccountItem::Single(governor)) = create_governor
+        .accounts
+        .iter_mut()
+        .find(|account| matches!(account, IdlInstructionAccountItem::Single(account) if account.name == "governor"))
+    else {
+        panic!("missing governor account");
+    };
+    governor.pda.as_mut().unwrap().seeds[1] = IdlSeed::Account(IdlSeedAccount {
+        path: "foo.bar".to_string(),
+        account: None,
+    });
+    let code = Generator::new(idl, &GeneratorConfig::default())
+        .unwrap()
+        .generate_cpi_interface()
+        .unwrap()
+        .to_string();
+    assert!(!code.contains("find_governor_address"));
+    assert!(code.contains("find_proposal_address"));
+}
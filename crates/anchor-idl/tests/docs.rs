@@ -0,0 +1,121 @@
+use anchor_idl::Builder;
+use syn::{Attribute, Fields, Item};
+
+fn generate_fixture() -> syn::File {
+    let code = Builder::new("../../examples/fixture/idl.json")
+        .config_path("../../examples/fixture/anchor-gen.toml")
+        .generate()
+        .unwrap();
+    syn::parse_file(&code).unwrap()
+}
+
+fn module_items<'a>(items: &'a [Item], name: &str) -> &'a [Item] {
+    items
+        .iter()
+        .find_map(|item| match item {
+            Item::Mod(module) if module.ident == name => {
+                module.content.as_ref().map(|(_, items)| &items[..])
+            }
+            _ => None,
+        })
+        .unwrap_or_else(|| panic!("missing module {}", name))
+}
+
+fn docs(attrs: &[Attribute]) -> Vec<String> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("doc"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(syn::Meta::NameValue(syn::MetaNameValue {
+                lit: syn::Lit::Str(doc),
+                ..
+            })) => Some(doc.value().trim().to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn named_field_docs(fields: &Fields, name: &str) -> Vec<String> {
+    let field = fields
+        .iter()
+        .find(|field| field.ident.as_ref().is_some_and(|ident| ident == name))
+        .unwrap_or_else(|| panic!("missing field {}", name));
+    docs(&field.attrs)
+}
+
+fn find_item<'a, T>(items: &'a [Item], find: impl Fn(&'a Item) -> Option<T>) -> T {
+    items.iter().find_map(find).unwrap()
+}
+
+#[test]
+fn docs_are_emitted_on_items() {
+    let file = generate_fixture();
+
+    let typedefs = module_items(&file.items, "typedefs");
+    let vault = find_item(typedefs, |item| match item {
+        Item::Struct(item) if item.ident == "VaultState" => Some(item),
+        _ => None,
+    });
+    assert_eq!(docs(&vault.attrs), ["Holds the deposits of an authority."]);
+    assert_eq!(
+        named_field_docs(&vault.fields, "authority"),
+        ["Authority which may withdraw from the vault."]
+    );
+    let either = find_item(typedefs, |item| match item {
+        Item::Enum(item) if item.ident == "Either" => Some(item),
+        _ => None,
+    });
+    let both = either
+        .variants
+        .iter()
+        .find(|variant| variant.ident == "Both")
+        .unwrap();
+    assert_eq!(
+        named_field_docs(&both.fields, "first"),
+        ["The value which was set first."]
+    );
+    let amount = find_item(typedefs, |item| match item {
+        Item::Type(item) if item.ident == "Amount" => Some(item),
+        _ => None,
+    });
+    assert_eq!(docs(&amount.attrs), ["An amount of tokens."]);
+
+    let errors = module_items(&file.items, "errors");
+    let error_code = find_item(errors, |item| match item {
+        Item::Enum(item) if item.ident == "ErrorCode" => Some(item),
+        _ => None,
+    });
+    assert_eq!(
+        docs(&error_code.variants[0].attrs),
+        ["6000 - The signer is not the authority of the vault."]
+    );
+
+    let instructions = module_items(&file.items, "instruction");
+    let deposit = find_item(instructions, |item| match item {
+        Item::Struct(item) if item.ident == "Deposit" => Some(item),
+        _ => None,
+    });
+    assert_eq!(docs(&deposit.attrs), ["Deposits into a vault."]);
+    assert_eq!(
+        named_field_docs(&deposit.fields, "amount"),
+        ["Amount of tokens to deposit."]
+    );
+
+    let accounts = module_items(&file.items, "accounts");
+    let deposit_accounts = find_item(accounts, |item| match item {
+        Item::Struct(item) if item.ident == "Deposit" => Some(item),
+        _ => None,
+    });
+    assert_eq!(docs(&deposit_accounts.attrs), ["Deposits into a vault."]);
+    assert_eq!(
+        named_field_docs(&deposit_accounts.fields, "vault"),
+        ["The vault to deposit into."]
+    );
+
+    let cpi = module_items(&file.items, "cpi");
+    let deposit_fn = find_item(cpi, |item| match item {
+        Item::Fn(item) if item.sig.ident == "deposit" => Some(item),
+        _ => None,
+    });
+    assert_eq!(docs(&deposit_fn.attrs), ["Deposits into a vault."]);
+}
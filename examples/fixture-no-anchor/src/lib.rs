@@ -0,0 +1,46 @@
+//! Generated client of the fixture IDL which does not depend on Anchor.
+//!
+//! Instructions are built from the [Pubkey]s of their accounts:
+//!
+//! ```
+//! use fixture_no_anchor::{accounts, instruction, state::VaultState, ID};
+//! use solana_program::pubkey::Pubkey;
+//!
+//! let deposit = instruction::deposit(
+//!     &accounts::Deposit {
+//!         vault: Pubkey::new_unique(),
+//!         authority: Pubkey::new_unique(),
+//!     },
+//!     5,
+//! );
+//! assert_eq!(deposit.program_id, ID);
+//! assert_eq!(deposit.accounts.len(), 3);
+//! assert!(deposit.accounts[1].is_signer);
+//! assert_eq!(&deposit.data[..8], instruction::Deposit::DISCRIMINATOR);
+//! assert_eq!(&deposit.data[8..], &5u64.to_le_bytes());
+//!
+//! // Accounts only have a discriminator, which is followed by their Borsh encoding.
+//! assert_eq!(VaultState::DISCRIMINATOR.len(), 8);
+//! ```
+//!
+//! Zero copy accounts have the same layout as with Anchor:
+//!
+//! ```
+//! use fixture_no_anchor::{state::Reserve, PodStatus, Status};
+//!
+//! assert_eq!(std::mem::size_of::<Reserve>(), 88);
+//! let reserve = Reserve {
+//!     status: Status::Frozen.into(),
+//!     ..Default::default()
+//! };
+//! let read: Reserve = bytemuck::pod_read_unaligned(bytemuck::bytes_of(&reserve));
+//! assert_eq!(read.status, PodStatus::from(Status::Frozen));
+//! ```
+
+#![allow(clippy::too_many_arguments)]
+
+anchor_gen::generate_cpi_interface!(
+    idl_path = "../fixture/idl.json",
+    config_path = "../fixture/anchor-gen.toml",
+    target = "no-anchor"
+);
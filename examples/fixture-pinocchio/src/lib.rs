@@ -0,0 +1,44 @@
+//! Generated client of the fixture IDL which uses Pinocchio.
+//!
+//! CPI helpers serialize the instruction data into a fixed size buffer, so they
+//! do not allocate:
+//!
+//! ```
+//! use fixture_pinocchio::{cpi, instruction, Amount, Pair, ID};
+//! use pinocchio::{account_info::AccountInfo, instruction::Signer, ProgramResult};
+//!
+//! let _: for<'a> fn(&cpi::accounts::Deposit<'a>, Amount, &[Signer]) -> ProgramResult =
+//!     cpi::deposit;
+//!
+//! // The maximum length of generic arguments is known once they are instantiated.
+//! let _: for<'a> fn(
+//!     &cpi::accounts::Rebalance<'a>,
+//!     &'a AccountInfo,
+//!     Pair<u16, 2>,
+//!     &[Signer],
+//! ) -> ProgramResult = cpi::rebalance;
+//! assert_eq!(instruction::Deposit::DISCRIMINATOR.len(), 8);
+//! let _: pinocchio::pubkey::Pubkey = ID;
+//! ```
+//!
+//! Zero copy accounts have the same layout as with Anchor:
+//!
+//! ```
+//! use fixture_pinocchio::{state::Reserve, PodStatus, Status};
+//!
+//! assert_eq!(std::mem::size_of::<Reserve>(), 88);
+//! let reserve = Reserve {
+//!     status: Status::Frozen.into(),
+//!     ..Default::default()
+//! };
+//! let read: Reserve = bytemuck::pod_read_unaligned(bytemuck::bytes_of(&reserve));
+//! assert_eq!(read.status, PodStatus::from(Status::Frozen));
+//! ```
+
+#![allow(clippy::too_many_arguments)]
+
+anchor_gen::generate_cpi_interface!(
+    idl_path = "../fixture/idl.json",
+    config_path = "../fixture/anchor-gen.toml",
+    target = "pinocchio"
+);
@@ -0,0 +1,429 @@
+//! Generated client of an IDL which covers the features of anchor-gen.
+//!
+//! The IDL is not of a deployed program. The examples below check the generated code,
+//! with a section for each feature.
+//!
+//! # Constants
+//!
+//! ```
+//! use fixture::constants::*;
+//!
+//! assert_eq!(VAULT_SEED, b"vault");
+//! assert_eq!(MAX_LABELS, 16);
+//! assert_eq!(MIN_RATE, -5);
+//! assert_eq!(NAME, "fixture");
+//! assert_eq!(
+//!     ADMIN.to_string(),
+//!     "CwL9JtAGfVeUZo4hmK8mNx5rHoZNaZwsMCvQAaMwoZUz"
+//! );
+//! assert_eq!(WEIGHTS, [1, 2, 3]);
+//! ```
+//!
+//! # Type aliases
+//!
+//! Aliases are generated as `type` items of the aliased type.
+//!
+//! ```
+//! use fixture::{Amount, Balances, Labels};
+//!
+//! let balances: Balances = [1, 2, 3, 4];
+//! let amount: Amount = balances[0];
+//! let labels: Labels = vec!["main".to_string()];
+//! let _: (u64, Vec<String>) = (amount, labels);
+//! ```
+//!
+//! # Tuple structs
+//!
+//! Types, accounts and events with unnamed fields are generated as tuple structs.
+//!
+//! ```
+//! use anchor_lang::prelude::Pubkey;
+//! use fixture::{events::Rebalanced, state::Registry, Point};
+//!
+//! let point = Point(1, 2);
+//! assert_eq!((point.0, point.1), (1, 2));
+//!
+//! let registry = Registry(Pubkey::new_unique(), 5);
+//! assert_eq!(registry.1, 5);
+//!
+//! let rebalanced = Rebalanced(registry.0, 100);
+//! assert_eq!(rebalanced.1, 100);
+//! ```
+//!
+//! # Generics
+//!
+//! Generic types keep their type and const parameters, and fields use the arguments
+//! from the IDL.
+//!
+//! ```
+//! use anchor_lang::{prelude::Pubkey, AnchorSerialize};
+//! use fixture::{state::VaultState, Either, Pair};
+//!
+//! let pair: Pair<u16, 2> = Pair {
+//!     items: [1, 2],
+//!     first: 1,
+//! };
+//! assert_eq!(pair.try_to_vec().unwrap().len(), 6);
+//!
+//! let delegate: Either<Pubkey> = Either::default();
+//! assert!(matches!(delegate, Either::Neither));
+//!
+//! // `VaultState::limits` is a `Pair<u64, 2>`.
+//! fn limits(vault: &VaultState) -> &[u64; 2] {
+//!     &vault.limits.items
+//! }
+//! let _ = limits;
+//! ```
+//!
+//! # 256-bit integers
+//!
+//! `u256` and `i256` are generated as [U256] and [I256], which are serialized as 32 little
+//! endian bytes.
+//!
+//! ```
+//! use anchor_lang::AnchorSerialize;
+//! use fixture::{
+//!     constants::{MIN_DELTA, TOTAL_SUPPLY},
+//!     Wide, I256, U256,
+//! };
+//!
+//! assert_eq!(TOTAL_SUPPLY, U256([0, 0, 1, 0]));
+//! assert_eq!(MIN_DELTA, I256::from(-1i128));
+//!
+//! let wide = Wide {
+//!     total: U256::from(5u128),
+//!     delta: I256::from(-5i128),
+//! };
+//! let data = wide.try_to_vec().unwrap();
+//! assert_eq!(data.len(), 64);
+//! assert_eq!(&data[..32], &U256::from(5u128).to_le_bytes());
+//! assert_eq!(&data[32..], &I256::from(-5i128).to_le_bytes());
+//! ```
+//!
+//! # Discriminators
+//!
+//! Discriminators are read from the IDL rather than derived from the names, so they are
+//! kept when a type is renamed.
+//!
+//! ```
+//! use anchor_lang::Discriminator;
+//! use fixture::{events::Deposited, instruction, state::VaultState};
+//!
+//! assert_eq!(
+//!     instruction::Deposit::DISCRIMINATOR,
+//!     &[242, 35, 198, 137, 82, 225, 242, 182]
+//! );
+//! assert_eq!(VaultState::DISCRIMINATOR, &[211, 8, 232, 43, 2, 152, 117, 119]);
+//! assert_eq!(Deposited::DISCRIMINATOR, &[111, 141, 26, 45, 161, 35, 100, 57]);
+//! ```
+//!
+//! # Account metas
+//!
+//! The structs in [accounts] hold the [Pubkey](anchor_lang::prelude::Pubkey) of each
+//! account and implement [ToAccountMetas](anchor_lang::ToAccountMetas). Missing optional
+//! accounts are replaced by the program ID.
+//!
+//! ```
+//! use anchor_lang::{prelude::Pubkey, ToAccountMetas};
+//! use fixture::accounts;
+//!
+//! let accounts = accounts::Rebalance {
+//!     authority: Pubkey::new_unique(),
+//!     Rebalancereserves: accounts::RebalanceReserves {
+//!         from: Pubkey::new_unique(),
+//!         to: None,
+//!     },
+//!     fee_receiver: None,
+//! };
+//! let metas = accounts.to_account_metas(None);
+//! assert_eq!(metas.len(), 4);
+//! assert_eq!(metas[0].pubkey, accounts.authority);
+//! assert!(metas[0].is_signer && !metas[0].is_writable);
+//! assert_eq!(metas[1].pubkey, accounts.Rebalancereserves.from);
+//! assert!(!metas[1].is_signer && metas[1].is_writable);
+//! assert_eq!(metas[2].pubkey, fixture::ID);
+//! assert!(!metas[2].is_writable);
+//! assert_eq!(metas[3].pubkey, fixture::ID);
+//! ```
+//!
+//! # Fixed addresses and relations
+//!
+//! Accounts with a fixed address are not fields of the struct, and are always passed
+//! with their address, unless they are optional. `new` sets optional accounts with a
+//! fixed address to it, and reads accounts which are related to another account from
+//! its data.
+//!
+//! ```
+//! use anchor_lang::{
+//!     prelude::{Clock, Pubkey},
+//!     solana_program::system_program,
+//!     solana_program::sysvar::SysvarId,
+//!     ToAccountMetas,
+//! };
+//! use fixture::{accounts, constants::ADMIN, state::VaultState, Either, Pair, Wide};
+//!
+//! let mut set_status = accounts::SetStatus::new(Pubkey::new_unique());
+//! assert_eq!(set_status.clock, Some(Clock::id()));
+//! let metas = set_status.to_account_metas(None);
+//! assert_eq!(metas.len(), 3);
+//! assert_eq!(metas[1].pubkey, ADMIN);
+//! assert!(metas[1].is_signer);
+//! assert_eq!(metas[2].pubkey, Clock::id());
+//!
+//! set_status.clock = None;
+//! assert_eq!(set_status.to_account_metas(None)[2].pubkey, fixture::ID);
+//!
+//! let vault_data = VaultState {
+//!     authority: Pubkey::new_unique(),
+//!     balances: [0; 4],
+//!     labels: vec![],
+//!     limits: Pair {
+//!         items: [0; 2],
+//!         first: 0,
+//!     },
+//!     delegate: Either::Neither,
+//!     wide: Wide::default(),
+//! };
+//! let vault = Pubkey::new_unique();
+//! let deposit = accounts::Deposit::new(vault, &vault_data);
+//! assert_eq!(deposit.authority, vault_data.authority);
+//! let metas = deposit.to_account_metas(None);
+//! assert_eq!(metas[0].pubkey, vault);
+//! assert_eq!(metas[2].pubkey, system_program::ID);
+//! ```
+//!
+//! # Return data
+//!
+//! CPI helpers of instructions which return data return a typed [cpi::Return], which reads
+//! the return data of the program.
+//!
+//! ```
+//! use anchor_lang::prelude::*;
+//! use fixture::{cpi, Amount};
+//!
+//! let _: for<'info> fn(
+//!     CpiContext<'_, '_, '_, 'info, cpi::accounts::Total<'info>>,
+//! ) -> Result<cpi::Return<Amount>> = cpi::total;
+//! ```
+//!
+//! # Config files
+//!
+//! `anchor-gen.toml` renames `Vault` to `VaultState` and derives additional traits.
+//!
+//! ```
+//! use anchor_lang::prelude::Pubkey;
+//! use fixture::{state::VaultState, Either, Pair, Point, Wide};
+//!
+//! fn is_eq<T: Eq>() {}
+//! is_eq::<Point>();
+//! assert_eq!(Point(1, 2), Point(1, 2));
+//!
+//! let vault = VaultState {
+//!     authority: Pubkey::new_unique(),
+//!     balances: [1, 2, 3, 4],
+//!     labels: vec!["main".to_string()],
+//!     limits: Pair {
+//!         items: [10, 20],
+//!         first: 10,
+//!     },
+//!     delegate: Either::One(Pubkey::new_unique()),
+//!     wide: Wide::default(),
+//! };
+//! assert!(vault == vault.clone());
+//! ```
+//!
+//! # Zero copy layouts
+//!
+//! `Reserve` has `bytemuck` serialization in the IDL, so it is generated as zero copy
+//! along with the types it references. The packed or aligned `repr` of a type is kept.
+//!
+//! ```
+//! use std::mem::{align_of, size_of};
+//!
+//! use fixture::{state::Reserve, Checkpoint, Curve, CurvePoint, Snapshot};
+//!
+//! assert_eq!(size_of::<CurvePoint>(), 8);
+//! assert_eq!(size_of::<Curve>(), 24);
+//! assert_eq!(size_of::<Checkpoint>(), 9);
+//! assert_eq!((size_of::<Snapshot>(), align_of::<Snapshot>()), (16, 16));
+//!
+//! let reserve = Reserve {
+//!     total: 100,
+//!     ..Default::default()
+//! };
+//! let data = bytemuck::bytes_of(&reserve);
+//! let read: &Reserve = bytemuck::from_bytes(data);
+//! assert_eq!(read.total, 100);
+//! ```
+//!
+//! # Zero copy enums
+//!
+//! `Status` is referenced by the zero copy `Reserve`, so it is stored there as a
+//! `PodStatus`. `FeeTier` has a `repr` and first discriminant in `anchor-gen.toml`.
+//!
+//! ```
+//! use anchor_lang::AnchorSerialize;
+//! use fixture::{state::Reserve, FeeTier, PodStatus, Status};
+//!
+//! assert_eq!(std::mem::size_of::<PodStatus>(), 1);
+//! let reserve = Reserve {
+//!     status: Status::Frozen.into(),
+//!     ..Default::default()
+//! };
+//! assert!(matches!(Status::try_from(reserve.status), Ok(Status::Frozen)));
+//! assert!(Status::try_from(PodStatus(2)).is_err());
+//!
+//! // `FeeTier` is a `repr(u64)` enum starting from 1.
+//! assert_eq!(FeeTier::Medium as u64, 2);
+//! assert_eq!(std::mem::size_of::<FeeTier>(), 8);
+//! assert_eq!(FeeTier::Medium.try_to_vec().unwrap(), vec![1]);
+//! ```
+//!
+//! # Layout checks
+//!
+//! The size of a zero copy struct and the offset of each field are computed from the
+//! IDL and checked at compile time, so a layout which drifts from the program fails to
+//! build instead of reading garbage.
+//!
+//! ```
+//! use fixture::state::Reserve;
+//!
+//! assert_eq!(std::mem::size_of::<Reserve>(), 88);
+//! assert_eq!(std::mem::offset_of!(Reserve, total), 40);
+//! ```
+//!
+//! # Events
+//!
+//! `Event` decodes the events written by `emit!`. `events::parse_logs` only decodes the
+//! lines logged while this program is executing, even if the program logs messages
+//! which look like the runtime's.
+//!
+//! ```
+//! use anchor_lang::{prelude::Pubkey, Event as _};
+//! use fixture::events::{parse_logs, Deposited, Event};
+//!
+//! let deposited = Deposited {
+//!     vault: Pubkey::new_unique(),
+//!     amount: 5,
+//! };
+//! let line = format!(
+//!     "Program data: {}",
+//!     anchor_lang::__private::base64::Engine::encode(
+//!         &anchor_lang::__private::base64::engine::general_purpose::STANDARD,
+//!         deposited.data(),
+//!     )
+//! );
+//! let Some(Event::Deposited(decoded)) = Event::try_from_log(&line).unwrap() else {
+//!     panic!("expected deposited");
+//! };
+//! assert_eq!((decoded.vault, decoded.amount), (deposited.vault, 5));
+//!
+//! let invoke = format!("Program {} invoke [1]", fixture::ID);
+//! let success = format!("Program {} success", fixture::ID);
+//! let logs = [
+//!     invoke.as_str(),
+//!     &line,
+//!     "Program log: success",
+//!     "Program log: invoke",
+//!     &line,
+//!     success.as_str(),
+//!     &line,
+//! ];
+//! assert_eq!(parse_logs(logs).unwrap().len(), 2);
+//! ```
+//!
+//! # Instruction decoding
+//!
+//! `Instruction::decode` decodes an instruction from its data and account keys,
+//! including composite and optional accounts. Like the program, it ignores trailing
+//! bytes of the data.
+//!
+//! ```
+//! use anchor_lang::{prelude::Pubkey, InstructionData, ToAccountMetas};
+//! use fixture::{accounts, instruction, Pair};
+//!
+//! let accounts = accounts::Rebalance {
+//!     authority: Pubkey::new_unique(),
+//!     Rebalancereserves: accounts::RebalanceReserves {
+//!         from: Pubkey::new_unique(),
+//!         to: None,
+//!     },
+//!     fee_receiver: Some(Pubkey::new_unique()),
+//! };
+//! let keys = accounts
+//!     .to_account_metas(None)
+//!     .iter()
+//!     .map(|meta| meta.pubkey)
+//!     .collect::<Vec<_>>();
+//!
+//! let args = instruction::Rebalance {
+//!     weights: Pair { items: [1, 2], first: 1 },
+//! };
+//! let mut data = args.data();
+//! data.extend_from_slice(&[0; 4]);
+//! let Some(instruction::Instruction::Rebalance {
+//!     args: decoded_args,
+//!     accounts: decoded_accounts,
+//! }) = instruction::Instruction::decode(&data, &keys).unwrap()
+//! else {
+//!     panic!("expected rebalance");
+//! };
+//! assert_eq!(decoded_args.weights.items, [1, 2]);
+//! assert_eq!(decoded_accounts, accounts);
+//!
+//! assert!(instruction::Instruction::decode(&args.data(), &keys[..2]).is_err());
+//! assert!(instruction::Instruction::decode(&[0; 8], &keys)
+//!     .unwrap()
+//!     .is_none());
+//! ```
+//!
+//! # Account decoding
+//!
+//! `AnyAccount` decodes any account of the program from its data, including zero copy
+//! accounts.
+//!
+//! ```
+//! use anchor_lang::{prelude::Pubkey, AccountSerialize, Discriminator};
+//! use fixture::{
+//!     state::{AnyAccount, Reserve, VaultState},
+//!     Either, Pair, Status, Wide,
+//! };
+//!
+//! let vault = VaultState {
+//!     authority: Pubkey::new_unique(),
+//!     balances: [1, 2, 3, 4],
+//!     labels: vec![],
+//!     limits: Pair { items: [1, 2], first: 1 },
+//!     delegate: Either::Neither,
+//!     wide: Wide::default(),
+//! };
+//! let mut data = vec![];
+//! vault.try_serialize(&mut data).unwrap();
+//! let Some(AnyAccount::VaultState(decoded)) = AnyAccount::try_from_bytes(&data).unwrap() else {
+//!     panic!("expected vault");
+//! };
+//! assert!(decoded == vault);
+//!
+//! // The data is unaligned and longer than the account.
+//! let reserve = Reserve {
+//!     status: Status::Frozen.into(),
+//!     total: 100,
+//!     ..Default::default()
+//! };
+//! let mut data = vec![0];
+//! data.extend_from_slice(Reserve::DISCRIMINATOR);
+//! data.extend_from_slice(bytemuck::bytes_of(&reserve));
+//! data.extend_from_slice(&[0; 16]);
+//! let Some(AnyAccount::Reserve(decoded)) = AnyAccount::try_from_bytes(&data[1..]).unwrap() else {
+//!     panic!("expected reserve");
+//! };
+//! assert_eq!(decoded.total, 100);
+//! assert!(matches!(Status::try_from(decoded.status), Ok(Status::Frozen)));
+//!
+//! assert!(AnyAccount::try_from_bytes(&data[1..40]).is_err());
+//! assert!(AnyAccount::try_from_bytes(&[0; 8]).unwrap().is_none());
+//! ```
+
+#![allow(clippy::too_many_arguments)]
+
+anchor_gen::generate_cpi_interface!(idl_path = "idl.json", config_path = "anchor-gen.toml");
@@ -9,45 +9,4 @@
 
 #![allow(clippy::too_many_arguments)]
 
-mod overrides;
-pub use overrides::*;
-
-anchor_gen::generate_cpi_interface!(
-    idl_path = "idl.json",
-    skip(UpdateConfigMode),
-    zero_copy(
-        UpdateLendingMarketConfigValue,
-        UpdateLendingMarketMode,
-        LastUpdate,
-        ElevationGroup,
-        InitObligationArgs,
-        ObligationCollateral,
-        ObligationLiquidity,
-        AssetTier,
-        BigFractionBytes,
-        FeeCalculation,
-        ReserveCollateral,
-        ReserveConfig,
-        ReserveFarmKind,
-        ReserveFees,
-        ReserveLiquidity,
-        ReserveStatus,
-        WithdrawalCaps,
-        PriceHeuristic,
-        PythConfiguration,
-        ScopeConfiguration,
-        SwitchboardConfiguration,
-        TokenInfo,
-        BorrowRateCurve,
-        CurvePoint,
-        UserState,
-        LendingMarket,
-        Obligation,
-        ReferrerState,
-        ReferrerTokenState,
-        UserMetadata,
-        Reserve,
-        Referrer,
-        ReferrerToken,
-    )
-);
+anchor_gen::generate_cpi_interface!(idl_path = "idl.json", config_path = "anchor-gen.toml");